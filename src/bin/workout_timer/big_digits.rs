@@ -0,0 +1,89 @@
+// Every glyph is drawn on a grid of 3 columns and 5 rows, '#' marks a filled pixel
+const GLYPH_HEIGHT: u16 = 5;
+// Terminal cells are about twice as high as wide, so a pixel takes two columns
+const PIXEL_WIDTH: u16 = 2;
+const FILLED: char = '█';
+
+fn get_glyph(character: char) -> [&'static str; GLYPH_HEIGHT as usize] {
+    match character {
+        '0' => ["###", "# #", "# #", "# #", "###"],
+        '1' => ["  #", "  #", "  #", "  #", "  #"],
+        '2' => ["###", "  #", "###", "#  ", "###"],
+        '3' => ["###", "  #", "###", "  #", "###"],
+        '4' => ["# #", "# #", "###", "  #", "  #"],
+        '5' => ["###", "#  ", "###", "  #", "###"],
+        '6' => ["###", "#  ", "###", "# #", "###"],
+        '7' => ["###", "  #", "  #", "  #", "  #"],
+        '8' => ["###", "# #", "###", "# #", "###"],
+        '9' => ["###", "# #", "###", "  #", "###"],
+        ':' => [" ", "#", " ", "#", " "],
+        _ => [" ", " ", " ", " ", " "],
+    }
+}
+
+// Text of the remaining seconds, hours are only shown when there are any
+pub fn get_clock_text(seconds: u32) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:0>2}:{:0>2}", hours, minutes, seconds)
+    } else {
+        format!("{:0>2}:{:0>2}", minutes, seconds)
+    }
+}
+
+// Lines of the text drawn with big digits, scaled up as much as the area allows.
+// The plain text is returned when even the smallest digits do not fit.
+pub fn get_lines(text: &str, width: u16, height: u16) -> Vec<String> {
+    let glyphs: Vec<[&str; GLYPH_HEIGHT as usize]> = text.chars().map(get_glyph).collect();
+    // Glyphs are separated by a single empty pixel
+    let pixels: u16 = glyphs
+        .iter()
+        .map(|glyph| glyph[0].len() as u16)
+        .sum::<u16>()
+        + glyphs.len().saturating_sub(1) as u16;
+    let scale = (width / (pixels * PIXEL_WIDTH)).min(height / GLYPH_HEIGHT);
+    if scale == 0 {
+        return vec![String::from(text)];
+    }
+    let pixel_width = (scale * PIXEL_WIDTH) as usize;
+    let mut lines: Vec<String> = Vec::new();
+    for row in 0..GLYPH_HEIGHT as usize {
+        let mut line = String::new();
+        for (index, glyph) in glyphs.iter().enumerate() {
+            if index > 0 {
+                line.push_str(&" ".repeat(pixel_width));
+            }
+            for pixel in glyph[row].chars() {
+                let cell = if pixel == '#' { FILLED } else { ' ' };
+                line.push_str(&cell.to_string().repeat(pixel_width));
+            }
+        }
+        for _ in 0..scale {
+            lines.push(line.clone());
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_clock_text, get_lines};
+
+    #[test]
+    fn clock_text_shows_hours_only_when_needed() {
+        assert_eq!(get_clock_text(75), "01:15");
+        assert_eq!(get_clock_text(3725), "1:02:05");
+    }
+
+    #[test]
+    fn digits_scale_to_the_area() {
+        // 4 digits and a colon take 17 pixels, each 2 columns wide
+        let lines = get_lines("00:15", 34, 5);
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0].chars().count(), 34);
+        let lines = get_lines("00:15", 110, 16);
+        assert_eq!(lines.len(), 15);
+        assert_eq!(lines[0].chars().count(), 102);
+        assert_eq!(get_lines("00:15", 33, 5), vec![String::from("00:15")]);
+    }
+}
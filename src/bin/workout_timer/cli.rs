@@ -0,0 +1,75 @@
+use std::path::PathBuf;
+
+use clap::{ArgGroup, Parser, Subcommand};
+
+use crate::user_options::{
+    EXCERCISE_QUANTITY_KEY, EXCERCISE_REST_TIME_KEY, EXCERCISE_TIME_KEY, PREPARE_TIME_KEY,
+    SET_PREPARE_TIME_KEY, SET_QUANTITY_KEY, SET_REST_TIME_KEY,
+};
+
+#[derive(Parser)]
+#[command(version, about = "Terminal timer for interval workouts")]
+#[command(group(ArgGroup::new("options").multiple(true)))]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Load the whole workout from a TOML or JSON file and start it right away
+    #[arg(long, value_name = "FILE", conflicts_with = "options")]
+    pub workout: Option<PathBuf>,
+    /// Excercise time in seconds
+    #[arg(long, value_name = "SECONDS", group = "options")]
+    pub work: Option<u32>,
+    /// Number of excercises per one set
+    #[arg(long, value_name = "NUMBER", group = "options")]
+    pub exercises: Option<u32>,
+    /// Rest time between excercises in seconds
+    #[arg(long, value_name = "SECONDS", group = "options")]
+    pub rest: Option<u32>,
+    /// Number of sets
+    #[arg(long, value_name = "NUMBER", group = "options")]
+    pub sets: Option<u32>,
+    /// Rest time between sets in seconds
+    #[arg(long, value_name = "SECONDS", group = "options")]
+    pub set_rest: Option<u32>,
+    /// Get ready time before the workout in seconds
+    #[arg(long, value_name = "SECONDS", group = "options")]
+    pub prepare: Option<u32>,
+    /// Get ready time after rest between sets in seconds
+    #[arg(long, value_name = "SECONDS", group = "options")]
+    pub set_prepare: Option<u32>,
+    /// Skip the options screen and start the workout right away
+    #[arg(long)]
+    pub start: bool,
+    /// Color theme: dark, light, high-contrast, monochrome or one from the config file
+    #[arg(long, value_name = "NAME")]
+    pub theme: Option<String>,
+    /// Directory with the WAV files played instead of the terminal bell
+    #[cfg(feature = "wav")]
+    #[arg(long, value_name = "DIRECTORY")]
+    pub sounds: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Print statistics of the recorded workouts
+    Stats,
+}
+
+impl Args {
+    // Option keys with flag names and values given on the command line
+    pub fn get_option_values(&self) -> Vec<(u8, &'static str, u32)> {
+        [
+            (EXCERCISE_TIME_KEY, "--work", self.work),
+            (EXCERCISE_QUANTITY_KEY, "--exercises", self.exercises),
+            (EXCERCISE_REST_TIME_KEY, "--rest", self.rest),
+            (SET_QUANTITY_KEY, "--sets", self.sets),
+            (SET_REST_TIME_KEY, "--set-rest", self.set_rest),
+            (PREPARE_TIME_KEY, "--prepare", self.prepare),
+            (SET_PREPARE_TIME_KEY, "--set-prepare", self.set_prepare),
+        ]
+        .into_iter()
+        .filter_map(|(option_key, flag, value)| value.map(|value| (option_key, flag, value)))
+        .collect()
+    }
+}
@@ -0,0 +1,95 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::PathBuf,
+};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use workout_timer::{timer::Timer, workout::WorkoutDefinition};
+
+const DATA_DIRECTORY: &str = "workout_timer";
+const HISTORY_FILE: &str = "history.jsonl";
+
+#[derive(Serialize, Deserialize)]
+pub struct Session {
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+    pub definition: WorkoutDefinition,
+    pub planned_sets: u32,
+    pub completed_sets: u32,
+    pub planned_excercises: u32,
+    pub completed_excercises: u32,
+    // Seconds of work and rest, warm up and cool down count as work and get ready as rest
+    #[serde(default)]
+    pub work_time: u32,
+    #[serde(default)]
+    pub rest_time: u32,
+    pub is_aborted: bool,
+    // Phase the workout was quit in, only for aborted sessions
+    pub aborted_phase: Option<String>,
+}
+
+impl Session {
+    pub fn new(start: DateTime<Local>, end: DateTime<Local>, timer: &Timer) -> Session {
+        let definition: &WorkoutDefinition = timer.get_definition();
+        let is_aborted = !timer.is_finished();
+        Session {
+            start,
+            end,
+            definition: definition.clone(),
+            planned_sets: definition.sets.len() as u32,
+            completed_sets: timer.get_completed_sets(),
+            planned_excercises: definition
+                .sets
+                .iter()
+                .map(|set| set.excercises.len() as u32)
+                .sum(),
+            completed_excercises: timer.get_completed_excercises(),
+            work_time: timer.get_work_time(),
+            rest_time: timer.get_rest_time(),
+            is_aborted,
+            aborted_phase: if is_aborted {
+                Some(timer.get_phase_name())
+            } else {
+                None
+            },
+        }
+    }
+}
+
+fn get_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|directory| directory.join(DATA_DIRECTORY).join(HISTORY_FILE))
+}
+
+// Every session is a single JSON line, so appending never rewrites the older records
+pub fn append(session: &Session) -> io::Result<()> {
+    let path =
+        get_path().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No data directory"))?;
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    let mut line = serde_json::to_string(session)?;
+    line.push('\n');
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())
+}
+
+// Lines that fail to parse are skipped, so one broken record does not hide the whole history
+pub fn load() -> io::Result<Vec<Session>> {
+    let path =
+        get_path().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No data directory"))?;
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+    let mut sessions: Vec<Session> = Vec::new();
+    for line in BufReader::new(file).lines() {
+        if let Ok(session) = serde_json::from_str(&line?) {
+            sessions.push(session);
+        }
+    }
+    Ok(sessions)
+}
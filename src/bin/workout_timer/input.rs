@@ -0,0 +1,202 @@
+use std::{
+    io,
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    thread,
+    time::Duration,
+};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{prelude::Backend, Terminal};
+#[cfg(unix)]
+use signal_hook::{
+    consts::{SIGHUP, SIGINT, SIGTERM, SIGTSTP},
+    iterator::Signals,
+};
+
+use crate::terminal;
+
+// Everything the screens react to, keys and signals come through a single channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputEvent {
+    Key(KeyEvent),
+    Resize,
+    // The timeout passed without any other event
+    Tick,
+    // Ctrl+C or a termination signal
+    Interrupt,
+    // Ctrl+Z or a stop signal
+    Suspend,
+}
+
+// Source of the events, so the screens can be driven by a script in tests
+pub trait Input {
+    // Waits up to the timeout for the next event, without a timeout until there is one
+    fn read(&mut self, timeout: Option<Duration>) -> io::Result<InputEvent>;
+
+    // Gives the terminal back to the shell until the app is resumed
+    fn suspend<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()>;
+}
+
+pub struct TerminalInput {
+    receiver: Receiver<io::Result<InputEvent>>,
+    is_interrupted: bool,
+}
+
+// Raw mode turns Ctrl+C and Ctrl+Z into key presses instead of signals
+fn from_key(key: KeyEvent) -> InputEvent {
+    if !key.modifiers.contains(KeyModifiers::CONTROL) {
+        return InputEvent::Key(key);
+    }
+    match key.code {
+        KeyCode::Char('c') => InputEvent::Interrupt,
+        KeyCode::Char('z') => InputEvent::Suspend,
+        _ => InputEvent::Key(key),
+    }
+}
+
+// Mouse and focus events are not used, there is no point in waking a screen up for them
+fn from_event(event: Event) -> Option<InputEvent> {
+    match event {
+        Event::Key(key) => Some(from_key(key)),
+        Event::Resize(_, _) => Some(InputEvent::Resize),
+        _ => None,
+    }
+}
+
+fn read_terminal(sender: Sender<io::Result<InputEvent>>) {
+    loop {
+        let event = match event::read() {
+            Ok(event) => from_event(event).map(Ok),
+            Err(error) => Some(Err(error)),
+        };
+        if let Some(event) = event {
+            let is_error = event.is_err();
+            // Nobody listens anymore once the app is leaving
+            if sender.send(event).is_err() || is_error {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn watch_signals(sender: Sender<io::Result<InputEvent>>) -> io::Result<()> {
+    let mut signals = Signals::new([SIGINT, SIGTERM, SIGHUP, SIGTSTP])?;
+    thread::spawn(move || {
+        for signal in signals.forever() {
+            let event = if signal == SIGTSTP {
+                InputEvent::Suspend
+            } else {
+                InputEvent::Interrupt
+            };
+            if sender.send(Ok(event)).is_err() {
+                return;
+            }
+        }
+    });
+    Ok(())
+}
+
+// Without job control Ctrl+C only comes as a key press
+#[cfg(not(unix))]
+fn watch_signals(_sender: Sender<io::Result<InputEvent>>) -> io::Result<()> {
+    Ok(())
+}
+
+impl TerminalInput {
+    // Starts the threads feeding the channel, one per app as the terminal has a single input
+    pub fn new() -> io::Result<TerminalInput> {
+        let (sender, receiver) = mpsc::channel();
+        watch_signals(sender.clone())?;
+        thread::spawn(move || read_terminal(sender));
+        Ok(TerminalInput {
+            receiver,
+            is_interrupted: false,
+        })
+    }
+}
+
+impl Input for TerminalInput {
+    fn read(&mut self, timeout: Option<Duration>) -> io::Result<InputEvent> {
+        // Every screen on the way out gets to see the interrupt, so the whole app quits
+        if self.is_interrupted {
+            return Ok(InputEvent::Interrupt);
+        }
+        let event = match timeout {
+            Some(timeout) => match self.receiver.recv_timeout(timeout) {
+                Ok(event) => event?,
+                Err(RecvTimeoutError::Timeout) => InputEvent::Tick,
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(io::Error::other("Terminal input has stopped"))
+                }
+            },
+            None => self
+                .receiver
+                .recv()
+                .map_err(|_| io::Error::other("Terminal input has stopped"))??,
+        };
+        self.is_interrupted = event == InputEvent::Interrupt;
+        Ok(event)
+    }
+
+    fn suspend<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
+        terminal::suspend()?;
+        // The shell showed the cursor and drew over the screen in the meantime
+        terminal.hide_cursor()?;
+        terminal.clear()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::{collections::VecDeque, io, time::Duration};
+
+    use crossterm::event::KeyEvent;
+    use ratatui::{prelude::Backend, Terminal};
+    use workout_timer::clock::{Clock, ManualClock};
+
+    use super::{from_key, Input, InputEvent};
+
+    // Presses keys at the given times, waiting moves the manual clock instead of sleeping
+    pub struct ScriptedInput {
+        clock: ManualClock,
+        events: VecDeque<(Duration, InputEvent)>,
+    }
+
+    impl ScriptedInput {
+        pub fn new<K: Into<KeyEvent> + Copy>(
+            clock: ManualClock,
+            keys: &[(u64, K)],
+        ) -> ScriptedInput {
+            let events = keys
+                .iter()
+                .map(|(time, key)| (Duration::from_millis(*time), from_key((*key).into())))
+                .collect();
+            ScriptedInput { clock, events }
+        }
+    }
+
+    impl Input for ScriptedInput {
+        fn read(&mut self, timeout: Option<Duration>) -> io::Result<InputEvent> {
+            let deadline = timeout.map(|timeout| self.clock.now() + timeout);
+            match (self.events.front(), deadline) {
+                (Some((time, _)), Some(deadline)) if *time > deadline => {
+                    self.clock.set(deadline);
+                    Ok(InputEvent::Tick)
+                }
+                (Some(&(time, event)), _) => {
+                    self.events.pop_front();
+                    self.clock.set(time);
+                    Ok(event)
+                }
+                // A script that never quits would loop forever
+                (None, _) => Err(io::Error::other("Script ended without quitting")),
+            }
+        }
+
+        // There is no shell to go to, the script goes on with the next key
+        fn suspend<B: Backend>(&mut self, _terminal: &mut Terminal<B>) -> io::Result<()> {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use ratatui::{
+    prelude::{Alignment, Backend, Rect},
+    text::Text,
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+use crate::theme::Theme;
+
+// Area of at most the given size in the middle of the given area
+pub fn center(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+pub fn is_too_small(area: Rect, width: u16, height: u16) -> bool {
+    area.width < width || area.height < height
+}
+
+// Shown instead of a screen that would be garbled, the keys keep working meanwhile
+pub fn too_small_tui<B: Backend>(frame: &mut Frame<B>, theme: &Theme, width: u16, height: u16) {
+    let size = frame.size();
+    let text = format!(
+        "Terminal too small\n{}x{}, needs {}x{}\nType q to exit",
+        size.width, size.height, width, height
+    );
+    let paragraph = Paragraph::new(Text::styled(text, theme.error))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    // Narrow terminals wrap the text into more lines
+    frame.render_widget(paragraph, center(size, size.width, 6));
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::prelude::Rect;
+
+    use super::{center, is_too_small};
+
+    #[test]
+    fn centered_area_fits_in_the_area() {
+        let area = Rect::new(0, 0, 120, 40);
+        assert_eq!(center(area, 100, 20), Rect::new(10, 10, 100, 20));
+        assert_eq!(center(area, 200, 50), area);
+        assert!(is_too_small(area, 121, 10));
+        assert!(!is_too_small(area, 120, 40));
+    }
+}
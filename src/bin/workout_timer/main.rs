@@ -0,0 +1,142 @@
+mod big_digits;
+mod cli;
+mod history;
+mod input;
+mod layout;
+mod presets;
+#[cfg(test)]
+mod snapshot;
+mod sound;
+mod stats;
+mod terminal;
+mod theme;
+mod timer;
+mod user_options;
+
+use std::{collections::HashMap, io, process};
+
+use chrono::Local;
+
+use clap::Parser;
+use ratatui::{prelude::Backend, Terminal};
+use workout_timer::{
+    clock::MonotonicClock,
+    timer::Timer,
+    workout::{self, Mode, WorkoutDefinition},
+};
+
+use cli::{Args, Command};
+use history::Session;
+use input::TerminalInput;
+use presets::Presets;
+use sound::{BellSink, Player};
+use stats::Stats;
+use theme::Theme;
+use user_options::Option;
+
+fn exit_with_error<T>(error: String) -> T {
+    eprintln!("{}", error);
+    process::exit(1);
+}
+
+#[cfg_attr(not(feature = "wav"), allow(unused_variables))]
+fn get_player(args: &Args) -> Player {
+    #[cfg(feature = "wav")]
+    if let Some(directory) = &args.sounds {
+        let sink = sound::WavSink::new(directory).unwrap_or_else(exit_with_error);
+        return Player::new(Box::new(sink));
+    }
+    Player::new(Box::new(BellSink))
+}
+
+fn run_workout<B: Backend>(
+    terminal: &mut Terminal<B>,
+    timer: Timer,
+    player: Player,
+    theme: &Theme,
+    input: &mut TerminalInput,
+) -> io::Result<()> {
+    let start = Local::now();
+    let timer: Timer = timer::run(
+        terminal,
+        timer,
+        player,
+        theme,
+        &MonotonicClock::new(),
+        input,
+    )
+    .expect("Failed to run timer");
+    history::append(&Session::new(start, Local::now(), &timer))
+}
+
+fn report_history_error(result: io::Result<()>) {
+    if let Err(error) = result {
+        eprintln!("Failed to save the workout history: {}", error);
+    }
+}
+
+fn main() {
+    let args: Args = Args::parse();
+    if let Some(Command::Stats) = args.command {
+        let sessions: Vec<Session> = history::load().unwrap_or_else(|error| {
+            exit_with_error(format!("Failed to read the workout history: {}", error))
+        });
+        Stats::new(&sessions, Local::now().date_naive()).print();
+        return;
+    }
+    let player: Player = get_player(&args);
+    let theme: Theme = Theme::load(args.theme.as_deref()).unwrap_or_else(exit_with_error);
+    if let Some(path) = &args.workout {
+        let definition: WorkoutDefinition = workout::load(path).unwrap_or_else(exit_with_error);
+        let timer: Timer = Timer::new(definition).unwrap_or_else(exit_with_error);
+        let mut terminal = terminal::get().expect("Failed to get terminal");
+        let mut input = TerminalInput::new().expect("Failed to read input");
+        let history_result = run_workout(&mut terminal, timer, player, &theme, &mut input);
+        terminal::restore(terminal).expect("Failed to restore terminal");
+        report_history_error(history_result);
+        return;
+    }
+    let mut presets: Presets = Presets::load().unwrap_or_else(exit_with_error);
+    let mut user_options: HashMap<u8, Option> = user_options::get_default();
+    user_options::set_values(&mut user_options, &presets.last);
+    let option_values = args.get_option_values();
+    // The flags are options of the intervals mode, whatever mode the last workout had
+    if !option_values.is_empty() {
+        user_options::set_mode(&mut user_options, Mode::Intervals);
+    }
+    for (option_key, flag, value) in option_values {
+        user_options::set_value(&mut user_options, option_key, value)
+            .unwrap_or_else(|error| exit_with_error(format!("Invalid {}: {}", flag, error)));
+    }
+    if args.start {
+        // The options screen checks the workout on Enter, without it the check happens here
+        user_options::get_definition(&user_options)
+            .validate()
+            .unwrap_or_else(exit_with_error);
+    }
+    let mut terminal = terminal::get().expect("Failed to get terminal");
+    let mut input = TerminalInput::new().expect("Failed to read input");
+    let mut user_options_status: u8 = 0;
+    let mut history_result: io::Result<()> = Ok(());
+    if !args.start {
+        let user_options_result: (u8, HashMap<u8, Option>) = user_options::get(
+            &mut terminal,
+            user_options,
+            &mut presets,
+            &theme,
+            &mut input,
+        );
+        user_options_status = user_options_result.0;
+        user_options = user_options_result.1;
+    }
+    if user_options_status == 0 {
+        presets.last = user_options::get_values(&user_options);
+        // Not remembering the options is not worth stopping the workout for
+        let _ = presets.save();
+        let timer: Timer = Timer::new(user_options::get_definition(&user_options))
+            .expect("Failed to create timer");
+        history_result = run_workout(&mut terminal, timer, player, &theme, &mut input);
+    }
+    terminal::restore(terminal).expect("Failed to restore terminal");
+    report_history_error(history_result);
+}
@@ -0,0 +1,68 @@
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+pub const CONFIG_DIRECTORY: &str = "workout_timer";
+const PRESETS_FILE: &str = "presets.toml";
+
+#[derive(Serialize, Deserialize)]
+pub struct Preset {
+    pub name: String,
+    pub values: BTreeMap<String, u32>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Presets {
+    // Options of the last started workout, used as defaults on the next start
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub last: BTreeMap<String, u32>,
+    #[serde(default)]
+    pub presets: Vec<Preset>,
+}
+
+fn get_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|directory| directory.join(CONFIG_DIRECTORY).join(PRESETS_FILE))
+}
+
+impl Presets {
+    // A missing file means no presets yet, a broken one is reported instead of being
+    // overwritten on the next save
+    pub fn load() -> Result<Presets, String> {
+        let Some(path) = get_path() else {
+            return Ok(Presets::default());
+        };
+        match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content).map_err(|error| {
+                format!(
+                    "Failed to parse {}, fix or remove it: {}",
+                    path.display(),
+                    error
+                )
+            }),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Presets::default()),
+            Err(error) => Err(format!("Failed to read {}: {}", path.display(), error)),
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = get_path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No config directory"))?;
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let content = toml::to_string(self).map_err(io::Error::other)?;
+        fs::write(path, content)
+    }
+
+    pub fn add(&mut self, name: String, values: BTreeMap<String, u32>) {
+        // Saving under an existing name replaces that preset
+        self.presets.retain(|preset| preset.name != name);
+        self.presets.push(Preset { name, values });
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.presets.len() {
+            self.presets.remove(index);
+        }
+    }
+}
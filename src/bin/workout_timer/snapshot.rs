@@ -0,0 +1,61 @@
+use std::{env, fs, path::PathBuf};
+
+use ratatui::{backend::TestBackend, buffer::Buffer, Frame, Terminal};
+
+const SNAPSHOTS_DIRECTORY: &str = "tests/snapshots";
+// Run the tests with this variable set to rewrite the golden files after an intended change
+const UPDATE_VARIABLE: &str = "UPDATE_SNAPSHOTS";
+
+// Terminal sizes every screen is checked at, from a small split pane to a big window
+pub const SIZES: [(u16, u16); 3] = [(60, 16), (80, 24), (120, 32)];
+
+pub fn render<F>(width: u16, height: u16, draw: F) -> Buffer
+where
+    F: FnOnce(&mut Frame<TestBackend>),
+{
+    let mut terminal =
+        Terminal::new(TestBackend::new(width, height)).expect("Failed to create test terminal");
+    terminal.draw(draw).expect("Failed to draw test terminal");
+    terminal.backend().buffer().clone()
+}
+
+// Only the text is compared, trailing spaces are dropped so editors do not break the files
+fn get_text(buffer: &Buffer) -> String {
+    let width = buffer.area.width as usize;
+    buffer
+        .content
+        .chunks(width)
+        .map(|row| {
+            let line: String = row.iter().map(|cell| cell.symbol.as_str()).collect();
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
+}
+
+pub fn assert_snapshot(name: &str, buffer: &Buffer) {
+    let actual = get_text(buffer);
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), SNAPSHOTS_DIRECTORY, name]
+        .iter()
+        .collect::<PathBuf>()
+        .with_extension("txt");
+    if env::var_os(UPDATE_VARIABLE).is_some() {
+        fs::create_dir_all(path.parent().unwrap()).expect("Failed to create snapshots directory");
+        fs::write(&path, actual).expect("Failed to write snapshot");
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "Missing snapshot {}, run the tests with {}=1 to create it",
+            path.display(),
+            UPDATE_VARIABLE
+        )
+    });
+    assert!(
+        actual == expected,
+        "Snapshot {} changed, run the tests with {}=1 to accept it\n--- expected\n{}--- actual\n{}",
+        name,
+        UPDATE_VARIABLE,
+        expected,
+        actual
+    );
+}
@@ -0,0 +1,143 @@
+use std::io::{self, Write};
+
+use workout_timer::timer::Cue;
+
+#[cfg(feature = "wav")]
+use rodio::Source;
+#[cfg(feature = "wav")]
+use std::{collections::HashMap, fs, io::Cursor, path::Path};
+
+pub trait Sink {
+    fn play(&mut self, cue: Cue);
+}
+
+pub struct BellSink;
+
+#[cfg(feature = "wav")]
+pub struct WavSink {
+    // The stream has to stay alive for the handle to produce any sound
+    _stream: rodio::OutputStream,
+    handle: rodio::OutputStreamHandle,
+    sounds: HashMap<Cue, Vec<u8>>,
+}
+
+pub struct Player {
+    sink: Box<dyn Sink>,
+    is_muted: bool,
+}
+
+#[cfg(feature = "wav")]
+const CUES: [Cue; 5] = [
+    Cue::WorkStart,
+    Cue::RestStart,
+    Cue::SetDone,
+    Cue::WorkoutDone,
+    Cue::Countdown,
+];
+
+#[cfg(feature = "wav")]
+fn get_file_name(cue: Cue) -> &'static str {
+    match cue {
+        Cue::WorkStart => "work_start.wav",
+        Cue::RestStart => "rest_start.wav",
+        Cue::SetDone => "set_done.wav",
+        Cue::WorkoutDone => "workout_done.wav",
+        Cue::Countdown => "countdown.wav",
+    }
+}
+
+impl Sink for BellSink {
+    fn play(&mut self, _cue: Cue) {
+        // Terminal bell has a single sound, a failed bell is not worth interrupting the workout
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+    }
+}
+
+#[cfg(feature = "wav")]
+impl WavSink {
+    pub fn new(directory: &Path) -> Result<WavSink, String> {
+        let mut sounds: HashMap<Cue, Vec<u8>> = HashMap::new();
+        for cue in CUES {
+            let path = directory.join(get_file_name(cue));
+            let sound = fs::read(&path)
+                .map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+            sounds.insert(cue, sound);
+        }
+        let (stream, handle) = rodio::OutputStream::try_default()
+            .map_err(|error| format!("Failed to open audio output: {}", error))?;
+        Ok(WavSink {
+            _stream: stream,
+            handle,
+            sounds,
+        })
+    }
+}
+
+#[cfg(feature = "wav")]
+impl Sink for WavSink {
+    fn play(&mut self, cue: Cue) {
+        let sound = Cursor::new(self.sounds[&cue].clone());
+        if let Ok(source) = rodio::Decoder::new_wav(sound) {
+            let _ = self.handle.play_raw(source.convert_samples());
+        }
+    }
+}
+
+impl Player {
+    pub fn new(sink: Box<dyn Sink>) -> Player {
+        Player {
+            sink,
+            is_muted: false,
+        }
+    }
+
+    pub fn play(&mut self, cue: Cue) {
+        if !self.is_muted {
+            self.sink.play(cue);
+        }
+    }
+
+    pub fn toggle_mute(&mut self) {
+        self.is_muted = !self.is_muted;
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.is_muted
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::{Cue, Player, Sink};
+
+    // Stores played cues instead of making any sound
+    pub struct RecordingSink {
+        pub cues: Rc<RefCell<Vec<Cue>>>,
+    }
+
+    impl Sink for RecordingSink {
+        fn play(&mut self, cue: Cue) {
+            self.cues.borrow_mut().push(cue);
+        }
+    }
+
+    pub fn get_recording_player() -> (Player, Rc<RefCell<Vec<Cue>>>) {
+        let cues: Rc<RefCell<Vec<Cue>>> = Rc::new(RefCell::new(Vec::new()));
+        let sink = RecordingSink { cues: cues.clone() };
+        (Player::new(Box::new(sink)), cues)
+    }
+
+    #[test]
+    fn muted_player_skips_cues() {
+        let (mut player, cues) = get_recording_player();
+        player.play(Cue::WorkStart);
+        player.toggle_mute();
+        player.play(Cue::RestStart);
+        player.toggle_mute();
+        player.play(Cue::Countdown);
+        assert_eq!(*cues.borrow(), vec![Cue::WorkStart, Cue::Countdown]);
+    }
+}
@@ -0,0 +1,258 @@
+use std::io;
+
+use chrono::{Datelike, Days, NaiveDate};
+use crossterm::event::KeyCode;
+use ratatui::{
+    prelude::{Backend, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::Text,
+    widgets::{BarChart, Block, Borders, Paragraph},
+    Frame, Terminal,
+};
+
+use crate::{
+    history::Session,
+    input::{Input, InputEvent},
+    theme::Theme,
+};
+
+const WEEKS: usize = 12;
+const COMMON_HEADER: &str = "Workout Timer developed by dev-michal-skiba\n\n";
+const STATS_HEADER: &str = "Type q or Esc to go back\n\n";
+
+struct Week {
+    start: NaiveDate,
+    sessions: u32,
+    work_time: u32,
+}
+
+// Statistics of the completed sessions, aborted ones are only counted
+pub struct Stats {
+    sessions: u32,
+    aborted_sessions: u32,
+    work_time: u32,
+    rest_time: u32,
+    current_streak: u32,
+    longest_streak: u32,
+    weeks: Vec<Week>,
+}
+
+fn get_week_start(date: NaiveDate) -> NaiveDate {
+    date - Days::new(date.weekday().num_days_from_monday() as u64)
+}
+
+fn get_time_text(time: u32) -> String {
+    format!("{}h {:02}m", time / 60 / 60, time / 60 % 60)
+}
+
+impl Stats {
+    pub fn new(sessions: &[Session], today: NaiveDate) -> Stats {
+        let completed: Vec<&Session> = sessions.iter().filter(|s| !s.is_aborted).collect();
+
+        let mut days: Vec<NaiveDate> = completed.iter().map(|s| s.start.date_naive()).collect();
+        days.sort();
+        days.dedup();
+        let mut longest_streak: u32 = 0;
+        let mut streak: u32 = 0;
+        for (index, day) in days.iter().enumerate() {
+            if index > 0 && days[index - 1].succ_opt() == Some(*day) {
+                streak += 1;
+            } else {
+                streak = 1;
+            }
+            longest_streak = longest_streak.max(streak);
+        }
+        // Streak is still alive when the last workout was yesterday
+        let current_streak: u32 = match days.last() {
+            Some(day) if *day == today || day.succ_opt() == Some(today) => streak,
+            _ => 0,
+        };
+
+        let last_week_start = get_week_start(today);
+        let weeks: Vec<Week> = (0..WEEKS)
+            .rev()
+            .map(|index| {
+                let start = last_week_start - Days::new(7 * index as u64);
+                let week_sessions: Vec<&&Session> = completed
+                    .iter()
+                    .filter(|s| get_week_start(s.start.date_naive()) == start)
+                    .collect();
+                Week {
+                    start,
+                    sessions: week_sessions.len() as u32,
+                    work_time: week_sessions.iter().map(|s| s.work_time).sum(),
+                }
+            })
+            .collect();
+
+        Stats {
+            sessions: completed.len() as u32,
+            aborted_sessions: (sessions.len() - completed.len()) as u32,
+            work_time: completed.iter().map(|s| s.work_time).sum(),
+            rest_time: completed.iter().map(|s| s.rest_time).sum(),
+            current_streak,
+            longest_streak,
+            weeks,
+        }
+    }
+
+    fn get_sessions_per_week(&self) -> f64 {
+        let sessions: u32 = self.weeks.iter().map(|week| week.sessions).sum();
+        sessions as f64 / WEEKS as f64
+    }
+
+    fn get_summary(&self) -> String {
+        format!(
+            "Completed sessions: {} (aborted: {})\n\
+             Total active time: {}\n\
+             Total rest time: {}\n\
+             Sessions per week in the last {} weeks: {:.1}\n\
+             Current streak: {} days\n\
+             Longest streak: {} days\n",
+            self.sessions,
+            self.aborted_sessions,
+            get_time_text(self.work_time),
+            get_time_text(self.rest_time),
+            WEEKS,
+            self.get_sessions_per_week(),
+            self.current_streak,
+            self.longest_streak,
+        )
+    }
+
+    pub fn print(&self) {
+        println!("{}", self.get_summary());
+        println!("Active minutes per week");
+        let max_minutes: u32 = self
+            .weeks
+            .iter()
+            .map(|w| w.work_time / 60)
+            .max()
+            .unwrap_or(0);
+        for week in &self.weeks {
+            let minutes: u32 = week.work_time / 60;
+            let width: usize = (minutes * 40).checked_div(max_minutes).unwrap_or(0) as usize;
+            println!(
+                "{} {:>4} {}",
+                week.start.format("%Y-%m-%d"),
+                minutes,
+                "#".repeat(width)
+            );
+        }
+    }
+}
+
+fn tui<B: Backend>(frame: &mut Frame<B>, stats: &Stats, theme: &Theme) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(11), Constraint::Min(0)].as_ref())
+        .split(frame.size());
+    let mut text = Text::styled(
+        COMMON_HEADER,
+        Style::default().add_modifier(Modifier::ITALIC),
+    );
+    text.extend(Text::raw(STATS_HEADER));
+    text.extend(Text::raw(stats.get_summary()));
+    frame.render_widget(Paragraph::new(text).block(Block::new()), chunks[0]);
+
+    let labels: Vec<String> = stats
+        .weeks
+        .iter()
+        .map(|week| week.start.format("%m-%d").to_string())
+        .collect();
+    let data: Vec<(&str, u64)> = stats
+        .weeks
+        .iter()
+        .zip(labels.iter())
+        .map(|(week, label)| (label.as_str(), (week.work_time / 60) as u64))
+        .collect();
+    let chart = BarChart::default()
+        .block(
+            Block::default()
+                .title(format!("Active minutes in the last {} weeks", WEEKS))
+                .borders(Borders::ALL),
+        )
+        .data(&data)
+        .bar_width(5)
+        .bar_gap(1)
+        .bar_style(theme.work)
+        .value_style(theme.work.add_modifier(Modifier::REVERSED));
+    frame.render_widget(chart, chunks[1]);
+}
+
+pub fn run<B: Backend>(
+    terminal: &mut Terminal<B>,
+    stats: &Stats,
+    theme: &Theme,
+    input: &mut impl Input,
+) -> io::Result<()> {
+    loop {
+        terminal.draw(|frame: &mut Frame<'_, B>| tui(frame, stats, theme))?;
+        // Nothing changes on its own, so the screen waits for the next key
+        match input.read(None)? {
+            InputEvent::Interrupt => return Ok(()),
+            InputEvent::Suspend => input.suspend(terminal)?,
+            InputEvent::Key(key) if matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) => {
+                return Ok(())
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Local, NaiveDate, TimeZone};
+
+    use super::Stats;
+    use workout_timer::workout::WorkoutDefinition;
+
+    use crate::history::Session;
+
+    fn get_session(year: i32, month: u32, day: u32, is_aborted: bool) -> Session {
+        let start = Local.with_ymd_and_hms(year, month, day, 8, 0, 0).unwrap();
+        Session {
+            start,
+            end: start,
+            definition: WorkoutDefinition::amrap(600, 0),
+            planned_sets: 1,
+            completed_sets: 1,
+            planned_excercises: 1,
+            completed_excercises: 1,
+            work_time: 600,
+            rest_time: 60,
+            is_aborted,
+            aborted_phase: None,
+        }
+    }
+
+    #[test]
+    fn streaks_count_consecutive_days_with_completed_sessions() {
+        let sessions = [
+            get_session(2024, 3, 1, false),
+            get_session(2024, 3, 2, false),
+            get_session(2024, 3, 3, false),
+            get_session(2024, 3, 5, false),
+            get_session(2024, 3, 6, true),
+            get_session(2024, 3, 8, false),
+            get_session(2024, 3, 9, false),
+            get_session(2024, 3, 9, false),
+        ];
+        let today = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let stats = Stats::new(&sessions, today);
+        assert_eq!(stats.sessions, 7);
+        assert_eq!(stats.aborted_sessions, 1);
+        assert_eq!(stats.work_time, 7 * 600);
+        assert_eq!(stats.longest_streak, 3);
+        assert_eq!(stats.current_streak, 2);
+        let last_week = stats.weeks.last().unwrap();
+        assert_eq!(
+            last_week.start,
+            NaiveDate::from_ymd_opt(2024, 3, 4).unwrap()
+        );
+        assert_eq!(last_week.sessions, 4);
+
+        let later = NaiveDate::from_ymd_opt(2024, 3, 12).unwrap();
+        assert_eq!(Stats::new(&sessions, later).current_streak, 0);
+    }
+}
@@ -0,0 +1,63 @@
+use crossterm::{
+    cursor::Show,
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{backend::CrosstermBackend, Terminal};
+#[cfg(unix)]
+use signal_hook::consts::SIGSTOP;
+use std::{io, panic};
+
+pub fn get() -> Result<Terminal<CrosstermBackend<io::Stdout>>, io::Error> {
+    set_panic_hook();
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let terminal: Terminal<CrosstermBackend<io::Stdout>> = Terminal::new(backend)?;
+    Ok(terminal)
+}
+
+pub fn restore(mut terminal: Terminal<CrosstermBackend<io::Stdout>>) -> Result<(), io::Error> {
+    reset()?;
+    terminal.show_cursor()?;
+    Ok(())
+}
+
+// Restores the terminal, stops the process like a shell would on Ctrl+Z and takes the terminal
+// back once the process is continued with fg
+#[cfg(unix)]
+pub fn suspend() -> Result<(), io::Error> {
+    reset()?;
+    // SIGTSTP is handled by the app, SIGSTOP can not be and stops it for real
+    signal_hook::low_level::raise(SIGSTOP)?;
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)
+}
+
+// There is no job control to give the terminal back to
+#[cfg(not(unix))]
+pub fn suspend() -> Result<(), io::Error> {
+    Ok(())
+}
+
+// Undoes everything get does, without the terminal it is also usable from the panic hook
+fn reset() -> Result<(), io::Error> {
+    disable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        Show
+    )
+}
+
+fn set_panic_hook() {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        // The panic message would be lost on the alternate screen
+        let _ = reset();
+        hook(info);
+    }));
+}
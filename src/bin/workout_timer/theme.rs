@@ -0,0 +1,386 @@
+use std::{collections::BTreeMap, env, fs, path::PathBuf, str::FromStr};
+
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+
+use crate::presets::CONFIG_DIRECTORY;
+
+const CONFIG_FILE: &str = "config.toml";
+const DEFAULT_THEME: &str = "dark";
+pub const THEMES: [&str; 4] = [DEFAULT_THEME, "light", "high-contrast", "monochrome"];
+// Red, green and blue of the basic colors as xterm shows them by default
+const BASIC_COLORS: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+// Levels of red, green and blue in the color cube of 256 color terminals
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+// Colors the terminal can show, true colors are kept as they are on the other ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColorSupport {
+    True,
+    Indexed,
+    Basic,
+}
+
+// Styles of everything colored on the screens
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub workout: Style,
+    // Set and EMOM interval gauges
+    pub set: Style,
+    // Excercises and other work
+    pub work: Style,
+    pub rest: Style,
+    pub prepare: Style,
+    pub warm_up: Style,
+    pub cool_down: Style,
+    // Active option and preset
+    pub active: Style,
+    pub error: Style,
+}
+
+// Colors of a theme from the config file, the missing ones come from the base theme
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct ThemeColors {
+    base: Option<String>,
+    workout: Option<String>,
+    set: Option<String>,
+    work: Option<String>,
+    rest: Option<String>,
+    prepare: Option<String>,
+    warm_up: Option<String>,
+    cool_down: Option<String>,
+    active: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    theme: Option<String>,
+    #[serde(default)]
+    themes: BTreeMap<String, ThemeColors>,
+}
+
+fn get_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|directory| directory.join(CONFIG_DIRECTORY).join(CONFIG_FILE))
+}
+
+fn get_style(color: Color) -> Style {
+    Style::default().fg(color)
+}
+
+fn set_color(style: &mut Style, color: &Option<String>) -> Result<(), String> {
+    if let Some(color) = color {
+        let color = Color::from_str(color).map_err(|_| format!("Invalid color {}", color))?;
+        *style = style.fg(color);
+    }
+    Ok(())
+}
+
+fn get_distance(
+    (red, green, blue): (u8, u8, u8),
+    (other_red, other_green, other_blue): (u8, u8, u8),
+) -> i32 {
+    [(red, other_red), (green, other_green), (blue, other_blue)]
+        .iter()
+        .map(|(value, other)| (i32::from(*value) - i32::from(*other)).pow(2))
+        .sum()
+}
+
+// Closest of the 16 basic colors, for terminals without 256 colors
+fn get_basic_color(color: Color) -> Color {
+    let Color::Rgb(red, green, blue) = color else {
+        return color;
+    };
+    BASIC_COLORS
+        .iter()
+        .min_by_key(|(_, rgb)| get_distance((red, green, blue), *rgb))
+        .map(|(basic, _)| *basic)
+        .unwrap_or(color)
+}
+
+// Closest color of the cube or the gray ramp of 256 color terminals
+fn get_indexed_color(color: Color) -> Color {
+    let Color::Rgb(red, green, blue) = color else {
+        return color;
+    };
+    let get_level = |value: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|level| (i32::from(CUBE_LEVELS[*level]) - i32::from(value)).abs())
+            .unwrap_or(0)
+    };
+    let (red_level, green_level, blue_level) = (get_level(red), get_level(green), get_level(blue));
+    let cube_rgb = (
+        CUBE_LEVELS[red_level],
+        CUBE_LEVELS[green_level],
+        CUBE_LEVELS[blue_level],
+    );
+    let cube_index = 16 + 36 * red_level + 6 * green_level + blue_level;
+    // The 24 grays go from 8 to 238 in steps of 10
+    let average = (u32::from(red) + u32::from(green) + u32::from(blue)) / 3;
+    let gray_step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray = 8 + 10 * gray_step;
+    if get_distance((red, green, blue), (gray, gray, gray))
+        < get_distance((red, green, blue), cube_rgb)
+    {
+        Color::Indexed(232 + gray_step)
+    } else {
+        Color::Indexed(cube_index as u8)
+    }
+}
+
+fn get_mapped_style(style: Style, map: fn(Color) -> Color) -> Style {
+    Style {
+        fg: style.fg.map(map),
+        bg: style.bg.map(map),
+        ..style
+    }
+}
+
+// Terminals announce true colors in COLORTERM and 256 colors in TERM, without TERM, like in the
+// Windows console, the colors are left to the terminal
+fn get_color_support() -> ColorSupport {
+    let colorterm = env::var("COLORTERM").unwrap_or_default();
+    if colorterm == "truecolor" || colorterm == "24bit" {
+        return ColorSupport::True;
+    }
+    match env::var("TERM") {
+        Ok(term) if term.ends_with("-direct") => ColorSupport::True,
+        Ok(term) if term.contains("256color") => ColorSupport::Indexed,
+        Ok(term) if !term.is_empty() => ColorSupport::Basic,
+        _ => ColorSupport::True,
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme::dark()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Theme {
+        Theme {
+            workout: get_style(Color::White),
+            set: get_style(Color::Rgb(255, 140, 0)),
+            work: get_style(Color::Red),
+            rest: get_style(Color::Green),
+            prepare: get_style(Color::Yellow),
+            warm_up: get_style(Color::Cyan),
+            cool_down: get_style(Color::Blue),
+            active: get_style(Color::LightGreen),
+            error: get_style(Color::Red),
+        }
+    }
+
+    // Darker colors readable on a white background
+    pub fn light() -> Theme {
+        Theme {
+            workout: get_style(Color::Black),
+            set: get_style(Color::Rgb(204, 102, 0)),
+            work: get_style(Color::Rgb(192, 0, 0)),
+            rest: get_style(Color::Rgb(0, 128, 0)),
+            prepare: get_style(Color::Rgb(153, 102, 0)),
+            warm_up: get_style(Color::Rgb(0, 128, 128)),
+            cool_down: get_style(Color::Blue),
+            active: get_style(Color::Blue).add_modifier(Modifier::BOLD),
+            error: get_style(Color::Rgb(192, 0, 0)),
+        }
+    }
+
+    // Only the bright basic colors, so it looks the same on every terminal
+    pub fn high_contrast() -> Theme {
+        Theme {
+            workout: get_style(Color::White),
+            set: get_style(Color::LightYellow),
+            work: get_style(Color::LightRed),
+            rest: get_style(Color::LightGreen),
+            prepare: get_style(Color::Yellow),
+            warm_up: get_style(Color::LightCyan),
+            cool_down: get_style(Color::LightBlue),
+            active: Style::default().fg(Color::Black).bg(Color::LightYellow),
+            error: get_style(Color::LightRed).add_modifier(Modifier::BOLD),
+        }
+    }
+
+    // Terminal colors only, the active option and errors stand out by the text style
+    pub fn monochrome() -> Theme {
+        Theme {
+            workout: Style::default(),
+            set: Style::default(),
+            work: Style::default(),
+            rest: Style::default(),
+            prepare: Style::default(),
+            warm_up: Style::default(),
+            cool_down: Style::default(),
+            active: Style::default().add_modifier(Modifier::REVERSED),
+            error: Style::default().add_modifier(Modifier::BOLD),
+        }
+    }
+
+    fn get_built_in(name: &str) -> Option<Theme> {
+        match name {
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            "monochrome" => Some(Theme::monochrome()),
+            _ => None,
+        }
+    }
+
+    fn from_colors(colors: &ThemeColors) -> Result<Theme, String> {
+        let base = colors.base.as_deref().unwrap_or(DEFAULT_THEME);
+        let mut theme = Theme::get_built_in(base).ok_or_else(|| {
+            format!(
+                "Unknown base theme {}, use one of {}",
+                base,
+                THEMES.join(", ")
+            )
+        })?;
+        set_color(&mut theme.workout, &colors.workout)?;
+        set_color(&mut theme.set, &colors.set)?;
+        set_color(&mut theme.work, &colors.work)?;
+        set_color(&mut theme.rest, &colors.rest)?;
+        set_color(&mut theme.prepare, &colors.prepare)?;
+        set_color(&mut theme.warm_up, &colors.warm_up)?;
+        set_color(&mut theme.cool_down, &colors.cool_down)?;
+        set_color(&mut theme.active, &colors.active)?;
+        set_color(&mut theme.error, &colors.error)?;
+        Ok(theme)
+    }
+
+    fn get_mapped(&self, map: fn(Color) -> Color) -> Theme {
+        Theme {
+            workout: get_mapped_style(self.workout, map),
+            set: get_mapped_style(self.set, map),
+            work: get_mapped_style(self.work, map),
+            rest: get_mapped_style(self.rest, map),
+            prepare: get_mapped_style(self.prepare, map),
+            warm_up: get_mapped_style(self.warm_up, map),
+            cool_down: get_mapped_style(self.cool_down, map),
+            active: get_mapped_style(self.active, map),
+            error: get_mapped_style(self.error, map),
+        }
+    }
+
+    // The theme given on the command line wins over NO_COLOR, which wins over the config file
+    fn select(
+        name: Option<&str>,
+        is_no_color: bool,
+        color_support: ColorSupport,
+        config: &Config,
+    ) -> Result<Theme, String> {
+        let name = match name {
+            Some(name) => name,
+            None if is_no_color => "monochrome",
+            None => config.theme.as_deref().unwrap_or(DEFAULT_THEME),
+        };
+        let theme = match config.themes.get(name) {
+            Some(colors) => Theme::from_colors(colors)
+                .map_err(|error| format!("Invalid theme {}: {}", name, error))?,
+            None => Theme::get_built_in(name).ok_or_else(|| {
+                format!(
+                    "Unknown theme {}, use one of {} or add it to the config file",
+                    name,
+                    THEMES.join(", ")
+                )
+            })?,
+        };
+        Ok(match color_support {
+            ColorSupport::True => theme,
+            ColorSupport::Indexed => theme.get_mapped(get_indexed_color),
+            ColorSupport::Basic => theme.get_mapped(get_basic_color),
+        })
+    }
+
+    // A missing config file means the built in themes only, a broken one is reported
+    pub fn load(name: Option<&str>) -> Result<Theme, String> {
+        let mut config = Config::default();
+        if let Some(path) = get_path() {
+            if let Ok(content) = fs::read_to_string(&path) {
+                config = toml::from_str(&content)
+                    .map_err(|error| format!("Failed to parse {}: {}", path.display(), error))?;
+            }
+        }
+        // Any value of NO_COLOR turns the colors off, see https://no-color.org
+        let is_no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Theme::select(name, is_no_color, get_color_support(), &config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::{Color, Style};
+
+    use super::{ColorSupport, Config, Theme};
+
+    #[test]
+    fn command_line_wins_over_no_color_and_config() {
+        let config: Config = toml::from_str("theme = \"light\"").unwrap();
+        assert_eq!(
+            Theme::select(None, false, ColorSupport::True, &config),
+            Ok(Theme::light())
+        );
+        assert_eq!(
+            Theme::select(None, true, ColorSupport::True, &config),
+            Ok(Theme::monochrome())
+        );
+        assert_eq!(
+            Theme::select(Some("high-contrast"), true, ColorSupport::True, &config),
+            Ok(Theme::high_contrast())
+        );
+        assert!(Theme::select(Some("neon"), false, ColorSupport::True, &config).is_err());
+    }
+
+    #[test]
+    fn user_themes_change_colors_of_the_base_theme() {
+        let config: Config = toml::from_str(
+            "theme = \"gym\"\n[themes.gym]\nbase = \"high-contrast\"\nwork = \"#ff00ff\"",
+        )
+        .unwrap();
+        let theme = Theme::select(None, false, ColorSupport::True, &config).unwrap();
+        assert_eq!(theme.work, Style::default().fg(Color::Rgb(255, 0, 255)));
+        assert_eq!(theme.rest, Theme::high_contrast().rest);
+        let config: Config = toml::from_str("[themes.gym]\nwork = \"neon\"").unwrap();
+        assert!(Theme::select(Some("gym"), false, ColorSupport::True, &config).is_err());
+    }
+
+    #[test]
+    fn true_colors_fall_back_to_what_the_terminal_supports() {
+        let theme = Theme::select(None, false, ColorSupport::Indexed, &Config::default()).unwrap();
+        assert_eq!(theme.set, Style::default().fg(Color::Indexed(208)));
+        assert_eq!(theme.work, Theme::dark().work);
+        let theme = Theme::select(
+            Some("light"),
+            false,
+            ColorSupport::Indexed,
+            &Config::default(),
+        );
+        assert_eq!(
+            theme.unwrap().warm_up,
+            Style::default().fg(Color::Indexed(30))
+        );
+        let theme = Theme::select(None, false, ColorSupport::Basic, &Config::default()).unwrap();
+        assert_eq!(theme.set, Style::default().fg(Color::Yellow));
+        assert_eq!(theme.work, Theme::dark().work);
+    }
+}
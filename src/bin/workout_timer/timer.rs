@@ -0,0 +1,680 @@
+use std::{io, time::Duration};
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    prelude::{Alignment, Backend, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::Text,
+    widgets::{Block, Borders, Gauge, Paragraph},
+    Frame, Terminal,
+};
+use workout_timer::{
+    clock::Clock,
+    timer::{get_short_time_text, get_time_text, Phase, Timer, TimerState},
+    workout::Mode,
+};
+
+use crate::{
+    big_digits,
+    input::{Input, InputEvent},
+    layout,
+    sound::Player,
+    theme::Theme,
+};
+
+// Gauges move smoothly between the seconds, the time itself comes from the start instant
+const DRAW_RATE: Duration = Duration::from_millis(100);
+const UP_NEXT_PHASES: usize = 3;
+// Below the minimal width nothing readable fits, below the compact size only one line gauges do
+const MIN_WIDTH: u16 = 20;
+const COMPACT_WIDTH: u16 = 40;
+const COMPACT_HEIGHT: u16 = 3 * MIN_GAUGE_HEIGHT + UP_NEXT_PHASES as u16 + 2;
+// The header is left out first when the height runs out
+const HEADER_HEIGHT: u16 = 5;
+const MIN_GAUGE_HEIGHT: u16 = 3;
+const MAX_GAUGE_HEIGHT: u16 = 5;
+// Wider gauges only spread the labels further apart
+const MAX_WIDTH: u16 = 100;
+const COMMON_HEADER: &str = "Workout Timer developed by dev-michal-skiba\n\n";
+const TIMER_HEADER: &str =
+    "Type q to exit, space to pause, n/b to skip/rewind, +/- to add/subtract 10s\nm to mute, c for a big clock, Ctrl+Z to suspend\n\n";
+const BIG_CLOCK_HEADER: &str = "Type c to go back to the gauges";
+
+fn get_title(timer: &Timer, is_muted: bool) -> String {
+    let mut title = String::from("Full Workout Timer");
+    if timer.is_paused() {
+        title.push_str(" - PAUSED, type space to continue");
+    }
+    if is_muted {
+        title.push_str(" - MUTED");
+    }
+    title
+}
+
+// Same style as the gauges of the phase
+fn get_phase_style(timer: &Timer, theme: &Theme) -> Style {
+    match timer.get_state() {
+        TimerState::WarmUp => theme.warm_up,
+        TimerState::CoolDown => theme.cool_down,
+        TimerState::Prepare => theme.prepare,
+        TimerState::Set => theme.work,
+        TimerState::ExcerciseRest | TimerState::SetRest => theme.rest,
+    }
+}
+
+// Other modes have no sets to show, the countdown leads to their first round or interval
+fn get_prepare_title(timer: &Timer) -> String {
+    let set = timer.get_set();
+    match timer.get_upcoming_phase() {
+        Some(phase) if timer.get_definition().mode != Mode::Intervals => format!(
+            "Get Ready For {} Timer",
+            phase.get_name(timer.get_definition())
+        ),
+        _ => format!("Get Ready For Set {}/{} Timer", set.current, set.quantity),
+    }
+}
+
+pub fn run<B: Backend>(
+    terminal: &mut Terminal<B>,
+    mut timer: Timer,
+    mut player: Player,
+    theme: &Theme,
+    clock: &impl Clock,
+    input: &mut impl Input,
+) -> io::Result<Timer> {
+    let start = clock.now();
+    let mut is_big_clock = false;
+    if let Some(cue) = timer.get_phase_cue() {
+        player.play(cue);
+    }
+    let mut is_changed = true;
+    loop {
+        if is_changed {
+            terminal.draw(|frame: &mut Frame<'_, B>| {
+                tui(frame, &timer, theme, player.is_muted(), is_big_clock)
+            })?;
+        }
+        // Ticks are only needed while the time moves, otherwise the screen waits for a key
+        let timeout = if timer.is_paused() || timer.is_finished() {
+            None
+        } else {
+            Some(DRAW_RATE)
+        };
+        let event = input.read(timeout)?;
+        // Catch up first so a key press applies to the time it was made at
+        for cue in timer.advance_to(clock.now() - start) {
+            player.play(cue);
+        }
+        is_changed = true;
+        match event {
+            InputEvent::Tick | InputEvent::Resize => {}
+            InputEvent::Interrupt => return Ok(timer),
+            InputEvent::Suspend => {
+                // The workout waits while in the shell and goes on with space after fg
+                if !timer.is_paused() {
+                    timer.toggle_pause();
+                }
+                input.suspend(terminal)?;
+            }
+            InputEvent::Key(key) => match key.code {
+                KeyCode::Char('q') => return Ok(timer),
+                KeyCode::Char(' ') => timer.toggle_pause(),
+                KeyCode::Char('n') | KeyCode::Right => timer.skip(),
+                KeyCode::Char('b') | KeyCode::Left => timer.rewind(),
+                KeyCode::Char('+') | KeyCode::Char('=') => timer.add_time(),
+                KeyCode::Char('-') => timer.subtract_time(),
+                KeyCode::Char('r') => timer.add_round(),
+                KeyCode::Char('d') => {
+                    if let Some(cue) = timer.complete() {
+                        player.play(cue);
+                    }
+                }
+                KeyCode::Char('m') => player.toggle_mute(),
+                KeyCode::Char('c') => is_big_clock = !is_big_clock,
+                // The next tick redraws anyway if the time moves
+                _ => is_changed = false,
+            },
+        }
+    }
+}
+
+fn get_gauge<'a>(title: String, style: Style, ratio: f64, label: String) -> Gauge<'a> {
+    // Block characters fill the gauge even when the style has no colors, like in monochrome
+    Gauge::default()
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .gauge_style(style.add_modifier(Modifier::BOLD))
+        .use_unicode(true)
+        .ratio(ratio)
+        .label(label)
+}
+
+fn tui<B: Backend>(
+    frame: &mut Frame<B>,
+    timer: &Timer,
+    theme: &Theme,
+    is_muted: bool,
+    is_big_clock: bool,
+) {
+    let size = frame.size();
+    if layout::is_too_small(size, MIN_WIDTH, 1) {
+        layout::too_small_tui(frame, theme, MIN_WIDTH, 1);
+        return;
+    }
+    if layout::is_too_small(size, COMPACT_WIDTH, COMPACT_HEIGHT) {
+        compact_tui(frame, timer, theme);
+        return;
+    }
+    if is_big_clock {
+        big_clock_tui(frame, timer, theme, is_muted);
+        return;
+    }
+    let header_height = if size.height >= COMPACT_HEIGHT + HEADER_HEIGHT {
+        HEADER_HEIGHT
+    } else {
+        0
+    };
+    let up_next_height = UP_NEXT_PHASES as u16 + 2;
+    // Gauges grow with the terminal, the whole screen stays in the middle of it
+    let gauge_height = ((size.height - header_height - up_next_height) / 3)
+        .clamp(MIN_GAUGE_HEIGHT, MAX_GAUGE_HEIGHT);
+    let area = layout::center(
+        size,
+        MAX_WIDTH,
+        header_height + 3 * gauge_height + up_next_height,
+    );
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(header_height),
+                Constraint::Length(gauge_height),
+                Constraint::Length(gauge_height),
+                Constraint::Length(gauge_height),
+                Constraint::Length(up_next_height),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+    // Header info
+    let mut text = Text::styled(
+        COMMON_HEADER,
+        Style::default().add_modifier(Modifier::ITALIC),
+    );
+    text.extend(Text::raw(TIMER_HEADER));
+    frame.render_widget(Paragraph::new(text).block(Block::new()), chunks[0]);
+    // Full Workout Timer
+    let gauge = get_gauge(
+        get_title(timer, is_muted),
+        theme.workout,
+        timer.get_workout().ratio,
+        timer.get_workout().get_label(),
+    );
+    frame.render_widget(gauge, chunks[1]);
+
+    match timer.get_state() {
+        TimerState::WarmUp => {
+            // Warm Up Timer
+            let gauge = get_gauge(
+                format!("Warm Up - {}", timer.get_warm_up().get_title()),
+                theme.warm_up,
+                timer.get_warm_up().ratio,
+                timer.get_warm_up().get_label(),
+            );
+            frame.render_widget(gauge, chunks[2]);
+        }
+        TimerState::CoolDown => {
+            // Cool Down Timer
+            let gauge = get_gauge(
+                format!("Cool Down - {}", timer.get_cool_down().get_title()),
+                theme.cool_down,
+                timer.get_cool_down().ratio,
+                timer.get_cool_down().get_label(),
+            );
+            frame.render_widget(gauge, chunks[2]);
+        }
+        TimerState::Prepare => {
+            // Get Ready Timer
+            let gauge = get_gauge(
+                get_prepare_title(timer),
+                theme.prepare,
+                timer.get_prepare().ratio,
+                timer.get_prepare().get_label(),
+            );
+            frame.render_widget(gauge, chunks[2]);
+        }
+        TimerState::Set | TimerState::ExcerciseRest | TimerState::SetRest => {
+            match timer.get_definition().mode {
+                Mode::Intervals => intervals_tui(frame, timer, theme, &chunks),
+                Mode::Tabata => tabata_tui(frame, timer, theme, &chunks),
+                Mode::Emom => emom_tui(frame, timer, theme, &chunks),
+                Mode::Amrap => amrap_tui(frame, timer, theme, &chunks),
+                Mode::ForTime => for_time_tui(frame, timer, theme, &chunks),
+            }
+        }
+    }
+    up_next_tui(frame, timer, chunks[4]);
+}
+
+// One line gauge of the phase for very small panes, followed by what fits of the rest
+fn compact_tui<B: Backend>(frame: &mut Frame<B>, timer: &Timer, theme: &Theme) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Min(0),
+            ]
+            .as_ref(),
+        )
+        .split(frame.size());
+    let mut label = format!(
+        "{} · {}",
+        timer.get_phase_name(),
+        big_digits::get_clock_text(timer.get_remaining_time())
+    );
+    if timer.is_paused() {
+        label.push_str(" · PAUSED");
+    }
+    let time = timer.get_phase().time;
+    let ratio = if time == 0 {
+        1.0
+    } else {
+        (timer.get_phase_elapsed().as_secs_f64() / f64::from(time)).min(1.0)
+    };
+    let gauge = Gauge::default()
+        .gauge_style(get_phase_style(timer, theme).add_modifier(Modifier::BOLD))
+        .use_unicode(true)
+        .ratio(ratio)
+        .label(label);
+    frame.render_widget(gauge, chunks[0]);
+    let next = match timer.get_upcoming_phase() {
+        Some(phase) => format!("Next: {}", get_phase_text(timer, phase)),
+        None => String::from("Last phase"),
+    };
+    frame.render_widget(Paragraph::new(next), chunks[1]);
+    let workout = format!("Workout: {}", timer.get_workout().get_label());
+    frame.render_widget(Paragraph::new(workout), chunks[2]);
+}
+
+fn get_phase_text(timer: &Timer, phase: &Phase) -> String {
+    format!(
+        "{} · {}",
+        phase.get_name(timer.get_definition()),
+        get_short_time_text(phase.time)
+    )
+}
+
+// Remaining time of the phase readable from across the room
+fn big_clock_tui<B: Backend>(frame: &mut Frame<B>, timer: &Timer, theme: &Theme, is_muted: bool) {
+    let style = get_phase_style(timer, theme);
+    let mut title = timer.get_phase_name();
+    if timer.is_paused() {
+        title.push_str(" - PAUSED, type space to continue");
+    }
+    if is_muted {
+        title.push_str(" - MUTED");
+    }
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(style);
+    let area = block.inner(frame.size());
+    frame.render_widget(block, frame.size());
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)].as_ref())
+        .split(area);
+    let lines = big_digits::get_lines(
+        &big_digits::get_clock_text(timer.get_remaining_time()),
+        chunks[0].width,
+        chunks[0].height,
+    );
+    // Centered vertically by the empty lines above the digits
+    let mut text =
+        Text::raw("\n".repeat((chunks[0].height as usize).saturating_sub(lines.len()) / 2));
+    text.extend(Text::styled(
+        lines.join("\n"),
+        style.add_modifier(Modifier::BOLD),
+    ));
+    frame.render_widget(Paragraph::new(text).alignment(Alignment::Center), chunks[0]);
+    let header = Text::styled(
+        BIG_CLOCK_HEADER,
+        Style::default().add_modifier(Modifier::ITALIC),
+    );
+    frame.render_widget(
+        Paragraph::new(header).alignment(Alignment::Center),
+        chunks[1],
+    );
+}
+
+// Next phases, so the rests can be used to get into position
+fn up_next_tui<B: Backend>(frame: &mut Frame<B>, timer: &Timer, area: Rect) {
+    let phases = timer.get_upcoming_phases(UP_NEXT_PHASES);
+    let mut text = Text::default();
+    for (index, phase) in phases.iter().enumerate() {
+        let content = format!(
+            "{}: {}",
+            if index == 0 { "Next" } else { "Then" },
+            get_phase_text(timer, phase)
+        );
+        if index == 0 {
+            text.extend(Text::styled(
+                content,
+                Style::default().add_modifier(Modifier::BOLD),
+            ));
+        } else {
+            text.extend(Text::raw(content));
+        }
+    }
+    if phases.is_empty() {
+        text.extend(Text::raw("Last phase, the workout ends after it"));
+    }
+    let paragraph =
+        Paragraph::new(text).block(Block::default().title("Up Next").borders(Borders::ALL));
+    frame.render_widget(paragraph, area);
+}
+
+fn intervals_tui<B: Backend>(frame: &mut Frame<B>, timer: &Timer, theme: &Theme, chunks: &[Rect]) {
+    if timer.get_state() == TimerState::SetRest {
+        // Set Rest Timer
+        let gauge = get_gauge(
+            String::from("Set Rest Timer"),
+            theme.rest,
+            timer.get_set_rest().ratio,
+            timer.get_set_rest().get_label(),
+        );
+        frame.render_widget(gauge, chunks[2]);
+        return;
+    }
+    // Set Timer
+    let gauge = get_gauge(
+        timer.get_set().get_title(),
+        theme.set,
+        timer.get_set().ratio,
+        timer.get_set().get_label(),
+    );
+    frame.render_widget(gauge, chunks[2]);
+    if timer.get_state() == TimerState::Set {
+        // Excercise Timer
+        let gauge = get_gauge(
+            timer.get_excercise().get_title(),
+            theme.work,
+            timer.get_excercise().ratio,
+            timer.get_excercise().get_label(),
+        );
+        frame.render_widget(gauge, chunks[3]);
+    } else {
+        // Excercise Rest Timer
+        let gauge = get_gauge(
+            String::from("Excercise Rest Timer"),
+            theme.rest,
+            timer.get_excercise_rest().ratio,
+            timer.get_excercise_rest().get_label(),
+        );
+        frame.render_widget(gauge, chunks[3]);
+    }
+}
+
+fn tabata_tui<B: Backend>(frame: &mut Frame<B>, timer: &Timer, theme: &Theme, chunks: &[Rect]) {
+    // Tabata is a single set, so the round gauge replaces the set gauge
+    if timer.get_state() == TimerState::Set {
+        let gauge = get_gauge(
+            timer.get_excercise().get_title(),
+            theme.work,
+            timer.get_excercise().ratio,
+            timer.get_excercise().get_label(),
+        );
+        frame.render_widget(gauge, chunks[2]);
+    } else {
+        let gauge = get_gauge(
+            format!(
+                "Tabata Rest {}/{} Timer",
+                timer.get_excercise().current,
+                timer.get_excercise().quantity
+            ),
+            theme.rest,
+            timer.get_excercise_rest().ratio,
+            timer.get_excercise_rest().get_label(),
+        );
+        frame.render_widget(gauge, chunks[2]);
+    }
+}
+
+fn emom_tui<B: Backend>(frame: &mut Frame<B>, timer: &Timer, theme: &Theme, chunks: &[Rect]) {
+    // Every set is one interval, so the set gauge shows the whole interval
+    let gauge = get_gauge(
+        format!(
+            "EMOM Interval {}/{} Timer",
+            timer.get_set().current,
+            timer.get_set().quantity
+        ),
+        theme.set,
+        timer.get_set().ratio,
+        timer.get_set().get_label(),
+    );
+    frame.render_widget(gauge, chunks[2]);
+    let (text, style) = if timer.get_state() == TimerState::Set {
+        (
+            "Work, type d when done to rest until the next interval",
+            theme.work,
+        )
+    } else {
+        ("Rest until the next interval", theme.rest)
+    };
+    let paragraph =
+        Paragraph::new(Text::styled(text, style)).block(Block::default().borders(Borders::ALL));
+    frame.render_widget(paragraph, chunks[3]);
+}
+
+fn amrap_tui<B: Backend>(frame: &mut Frame<B>, timer: &Timer, theme: &Theme, chunks: &[Rect]) {
+    let time = &timer.get_excercise().time;
+    let gauge = get_gauge(
+        String::from("AMRAP Timer"),
+        theme.work,
+        timer.get_excercise().ratio,
+        format!("{} left", get_time_text(time.max - time.current)),
+    );
+    frame.render_widget(gauge, chunks[2]);
+    let text = format!(
+        "Rounds completed: {}, type r to add a round",
+        timer.get_rounds()
+    );
+    let paragraph = Paragraph::new(text).block(Block::default().borders(Borders::ALL));
+    frame.render_widget(paragraph, chunks[3]);
+}
+
+fn for_time_tui<B: Backend>(frame: &mut Frame<B>, timer: &Timer, theme: &Theme, chunks: &[Rect]) {
+    let gauge = get_gauge(
+        String::from("For Time Timer"),
+        theme.work,
+        timer.get_excercise().ratio,
+        format!(
+            "{} (time cap {})",
+            timer.get_excercise().time.current_text,
+            timer.get_excercise().time.max_text
+        ),
+    );
+    frame.render_widget(gauge, chunks[2]);
+    let text = if timer.get_excercise().is_finished {
+        format!(
+            "Finished in {} with {} rounds",
+            timer.get_excercise().time.current_text,
+            timer.get_rounds()
+        )
+    } else {
+        format!(
+            "Rounds completed: {}, type r to add a round and d when done",
+            timer.get_rounds()
+        )
+    };
+    let paragraph = Paragraph::new(text).block(Block::default().borders(Borders::ALL));
+    frame.render_widget(paragraph, chunks[3]);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{backend::TestBackend, Terminal};
+    use workout_timer::{
+        clock::ManualClock,
+        timer::{Cue, Timer, TimerState},
+        workout::WorkoutDefinition,
+    };
+
+    use super::{run, tui};
+    use crate::{
+        input::tests::ScriptedInput,
+        snapshot::{self, SIZES},
+        sound::tests::get_recording_player,
+        theme::Theme,
+    };
+
+    // Runs the timer screen with scripted key presses and a clock that only moves while waiting
+    fn simulate<K: Into<KeyEvent> + Copy>(
+        definition: WorkoutDefinition,
+        keys: &[(u64, K)],
+    ) -> (Timer, Vec<Cue>) {
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        let clock = ManualClock::new();
+        let mut input = ScriptedInput::new(clock.clone(), keys);
+        let (player, cues) = get_recording_player();
+        let timer = run(
+            &mut terminal,
+            Timer::new(definition).unwrap(),
+            player,
+            &Theme::default(),
+            &clock,
+            &mut input,
+        )
+        .unwrap();
+        let cues = cues.borrow().clone();
+        (timer, cues)
+    }
+
+    #[test]
+    fn skip_and_rewind_apply_at_the_time_of_the_key_press() {
+        let definition = WorkoutDefinition::from_options(10, 2, 5, 1, 0, 0, 0);
+        let keys = [
+            (3_000, KeyCode::Char('n')),
+            (4_000, KeyCode::Left),
+            (6_000, KeyCode::Right),
+            (7_500, KeyCode::Char('q')),
+        ];
+        let (timer, cues) = simulate(definition, &keys);
+        assert_eq!(timer.get_state(), TimerState::ExcerciseRest);
+        assert_eq!(timer.get_phase_elapsed(), Duration::from_millis(1500));
+        assert_eq!(timer.get_work_time(), 5);
+        assert_eq!(timer.get_rest_time(), 2);
+        // Skipped phases never reach their countdown
+        assert_eq!(cues, vec![Cue::WorkStart]);
+    }
+
+    #[test]
+    fn paused_time_is_not_counted() {
+        let definition = WorkoutDefinition::from_options(5, 1, 0, 1, 0, 0, 0);
+        let keys = [
+            (2_000, KeyCode::Char(' ')),
+            (10_000, KeyCode::Char(' ')),
+            (12_500, KeyCode::Char('q')),
+        ];
+        let (timer, cues) = simulate(definition, &keys);
+        assert!(!timer.is_finished());
+        assert_eq!(timer.get_phase_time(), 4);
+        let mut expected: Vec<Cue> = vec![Cue::WorkStart];
+        expected.extend([Cue::Countdown; 3]);
+        assert_eq!(cues, expected);
+    }
+
+    #[test]
+    fn suspending_pauses_the_workout() {
+        let definition = WorkoutDefinition::from_options(5, 1, 0, 1, 0, 0, 0);
+        let keys = [
+            (
+                2_000,
+                KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL),
+            ),
+            (60_000, KeyEvent::from(KeyCode::Char(' '))),
+            (
+                61_000,
+                KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            ),
+        ];
+        let (timer, _) = simulate(definition, &keys);
+        assert!(!timer.is_finished());
+        assert!(!timer.is_paused());
+        assert_eq!(timer.get_phase_time(), 3);
+    }
+
+    fn assert_snapshots(name: &str, definition: WorkoutDefinition, elapsed: u64) {
+        let mut timer = Timer::new(definition).unwrap();
+        timer.advance(Duration::from_secs(elapsed));
+        for (width, height) in SIZES {
+            let buffer = snapshot::render(width, height, |frame| {
+                tui(frame, &timer, &Theme::default(), false, false)
+            });
+            snapshot::assert_snapshot(&format!("timer_{}_{}x{}", name, width, height), &buffer);
+        }
+    }
+
+    #[test]
+    fn intervals_screens_match_snapshots() {
+        let definition = WorkoutDefinition::from_options(45, 10, 15, 3, 120, 10, 0);
+        assert_snapshots("prepare", definition.clone(), 3);
+        assert_snapshots("excercise", definition.clone(), 30);
+        assert_snapshots("excercise_rest", definition.clone(), 60);
+        assert_snapshots("set_rest", definition, 600);
+    }
+
+    #[test]
+    fn mode_screens_match_snapshots() {
+        assert_snapshots("tabata", WorkoutDefinition::tabata(20, 10, 8, 0), 25);
+        assert_snapshots("emom", WorkoutDefinition::emom(60, 10, 0), 70);
+        assert_snapshots("emom_prepare", WorkoutDefinition::emom(60, 10, 10), 3);
+        assert_snapshots("amrap", WorkoutDefinition::amrap(1200, 0), 90);
+        assert_snapshots("for_time", WorkoutDefinition::for_time(1200, 0), 90);
+    }
+
+    #[test]
+    fn small_and_large_screens_match_snapshots() {
+        let definition = WorkoutDefinition::from_options(45, 10, 15, 3, 120, 10, 0);
+        let mut timer = Timer::new(definition).unwrap();
+        timer.advance(Duration::from_secs(30));
+        // Too small, one line, compact and scaled up on a big monitor
+        for (width, height) in [(15, 4), (40, 1), (36, 8), (200, 60)] {
+            let buffer = snapshot::render(width, height, |frame| {
+                tui(frame, &timer, &Theme::default(), false, false)
+            });
+            snapshot::assert_snapshot(&format!("timer_excercise_{}x{}", width, height), &buffer);
+        }
+    }
+
+    #[test]
+    fn monochrome_gauges_are_filled() {
+        let mut timer =
+            Timer::new(WorkoutDefinition::from_options(45, 10, 15, 3, 120, 10, 0)).unwrap();
+        timer.advance(Duration::from_secs(30));
+        let buffer = snapshot::render(80, 24, |frame| {
+            tui(frame, &timer, &Theme::monochrome(), false, false)
+        });
+        // Without colors only the block characters show how far the gauges are
+        assert!(buffer.content.iter().any(|cell| cell.symbol == "█"));
+        snapshot::assert_snapshot("timer_monochrome_80x24", &buffer);
+    }
+
+    #[test]
+    fn big_clock_screens_match_snapshots() {
+        let mut timer =
+            Timer::new(WorkoutDefinition::from_options(45, 10, 15, 3, 120, 10, 0)).unwrap();
+        timer.advance(Duration::from_secs(30));
+        for (width, height) in SIZES {
+            let buffer = snapshot::render(width, height, |frame| {
+                tui(frame, &timer, &Theme::default(), false, true)
+            });
+            snapshot::assert_snapshot(&format!("timer_big_clock_{}x{}", width, height), &buffer);
+        }
+    }
+}
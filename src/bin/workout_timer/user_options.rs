@@ -0,0 +1,1050 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    prelude::{Backend, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Text},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    Frame, Terminal,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+};
+
+use chrono::Local;
+use workout_timer::{
+    plan::Plan,
+    timer::{get_short_time_text, get_time_text},
+    workout::{Mode, WorkoutDefinition, MODES},
+};
+
+use crate::{
+    history,
+    input::{Input, InputEvent},
+    layout,
+    presets::Presets,
+    stats::{self, Stats},
+    theme::Theme,
+};
+
+static DEFAULT_MODE_NAME: &str = "Workout mode";
+static DEFAULT_EXCERCISE_TIME_NAME: &str = "Excercise time";
+static DEFAULT_EXCERCISE_QUANTITY_NAME: &str = "Number of excercises per onse set";
+static DEFAULT_EXCERCISE_REST_TIME_NAME: &str = "Rest time between excercises";
+static DEFAULT_SET_QUANTITY_NAME: &str = "Number of sets";
+static DEFAULT_SET_REST_TIME_NAME: &str = "Rest time between sets";
+static DEFAULT_PREPARE_TIME_NAME: &str = "Get ready time before the workout";
+static DEFAULT_SET_PREPARE_TIME_NAME: &str = "Get ready time after rest between sets";
+static DEFAULT_TABATA_WORK_TIME_NAME: &str = "Tabata work time";
+static DEFAULT_TABATA_REST_TIME_NAME: &str = "Tabata rest time";
+static DEFAULT_TABATA_ROUNDS_NAME: &str = "Number of tabata rounds";
+static DEFAULT_EMOM_INTERVAL_TIME_NAME: &str = "EMOM interval time";
+static DEFAULT_EMOM_INTERVALS_NAME: &str = "Number of EMOM intervals";
+static DEFAULT_AMRAP_TIME_NAME: &str = "AMRAP time";
+static DEFAULT_FOR_TIME_CAP_NAME: &str = "For time cap";
+
+const DEFAULT_EXCERCISE_TIME_VALUE: u32 = 45;
+const DEFAULT_EXCERCISE_QUANTITY_VALUE: u32 = 10;
+const DEFAULT_EXCERCISE_REST_TIME_VALUE: u32 = 15;
+const DEFAULT_SET_QUANTITY_VALUE: u32 = 3;
+const DEFAULT_SET_REST_TIME_VALUE: u32 = 120;
+const DEFAULT_PREPARE_TIME_VALUE: u32 = 10;
+const DEFAULT_SET_PREPARE_TIME_VALUE: u32 = 0;
+const DEFAULT_TABATA_WORK_TIME_VALUE: u32 = 20;
+const DEFAULT_TABATA_REST_TIME_VALUE: u32 = 10;
+const DEFAULT_TABATA_ROUNDS_VALUE: u32 = 8;
+const DEFAULT_EMOM_INTERVAL_TIME_VALUE: u32 = 60;
+const DEFAULT_EMOM_INTERVALS_VALUE: u32 = 10;
+const DEFAULT_AMRAP_TIME_VALUE: u32 = 1200;
+const DEFAULT_FOR_TIME_CAP_VALUE: u32 = 1200;
+
+pub const EXCERCISE_TIME_KEY: u8 = 0;
+pub const EXCERCISE_QUANTITY_KEY: u8 = 1;
+pub const EXCERCISE_REST_TIME_KEY: u8 = 2;
+pub const SET_QUANTITY_KEY: u8 = 3;
+pub const SET_REST_TIME_KEY: u8 = 4;
+pub const PREPARE_TIME_KEY: u8 = 5;
+pub const SET_PREPARE_TIME_KEY: u8 = 6;
+pub const MODE_KEY: u8 = 7;
+pub const TABATA_WORK_TIME_KEY: u8 = 8;
+pub const TABATA_REST_TIME_KEY: u8 = 9;
+pub const TABATA_ROUNDS_KEY: u8 = 10;
+pub const EMOM_INTERVAL_TIME_KEY: u8 = 11;
+pub const EMOM_INTERVALS_KEY: u8 = 12;
+pub const AMRAP_TIME_KEY: u8 = 13;
+pub const FOR_TIME_CAP_KEY: u8 = 14;
+
+pub struct Option {
+    pub name: String,
+    pub value: u32,
+    pub min: u32,
+    pub max: u32,
+}
+
+struct State {
+    active_index: usize,
+    preset_index: usize,
+    is_presets_active: bool,
+    // Name typed for a new preset, present only while saving one
+    preset_name: std::option::Option<String>,
+    // Value typed for the active option, present only while typing one
+    input: std::option::Option<String>,
+    plan_scroll: u16,
+    message: String,
+}
+
+const COMMON_HEADER: &str = "Workout Timer developed by dev-michal-skiba\n\n";
+const USER_OPTIONS_HEADER: &str =
+    "Please use AWSD or arrows to change the options\nUse PageUp/PageDown or Shift for bigger steps\nType digits or mm:ss, Home/End for the limits\nType Enter to accept, t to see statistics, q to exit\n";
+const PRESETS_HEADER: &str =
+    "Type Tab to switch to presets, x to delete a preset\nType p to save the options as a preset\n\n";
+// Replaces the full header when the options would not fit with it
+const SHORT_HEADER: &str = "Type Enter to accept, q to exit";
+const HEADER_HEIGHT: u16 = 9;
+// Room below the options for the preset name or a message
+const MESSAGE_HEIGHT: u16 = 2;
+const MIN_WIDTH: u16 = 40;
+// Presets and the plan are left out when narrower, so the options are never cut off
+const SIDE_MIN_WIDTH: u16 = 28;
+// Share of the screen taken by the presets and the plan when the options leave enough room
+const SIDE_PERCENTAGE: u16 = 35;
+// Wider or higher screens only spread the options and the plan further apart
+const MAX_WIDTH: u16 = 140;
+const MAX_HEIGHT: u16 = 50;
+const INTERVALS_OPTION_KEYS_LIST: [u8; 8] = [
+    MODE_KEY,
+    EXCERCISE_TIME_KEY,
+    EXCERCISE_QUANTITY_KEY,
+    EXCERCISE_REST_TIME_KEY,
+    SET_QUANTITY_KEY,
+    SET_REST_TIME_KEY,
+    PREPARE_TIME_KEY,
+    SET_PREPARE_TIME_KEY,
+];
+const TABATA_OPTION_KEYS_LIST: [u8; 5] = [
+    MODE_KEY,
+    TABATA_WORK_TIME_KEY,
+    TABATA_REST_TIME_KEY,
+    TABATA_ROUNDS_KEY,
+    PREPARE_TIME_KEY,
+];
+const EMOM_OPTION_KEYS_LIST: [u8; 4] = [
+    MODE_KEY,
+    EMOM_INTERVAL_TIME_KEY,
+    EMOM_INTERVALS_KEY,
+    PREPARE_TIME_KEY,
+];
+const AMRAP_OPTION_KEYS_LIST: [u8; 3] = [MODE_KEY, AMRAP_TIME_KEY, PREPARE_TIME_KEY];
+const FOR_TIME_OPTION_KEYS_LIST: [u8; 3] = [MODE_KEY, FOR_TIME_CAP_KEY, PREPARE_TIME_KEY];
+const TIME_OPTION_KEYS_LIST: [u8; 10] = [
+    EXCERCISE_TIME_KEY,
+    EXCERCISE_REST_TIME_KEY,
+    SET_REST_TIME_KEY,
+    PREPARE_TIME_KEY,
+    SET_PREPARE_TIME_KEY,
+    TABATA_WORK_TIME_KEY,
+    TABATA_REST_TIME_KEY,
+    EMOM_INTERVAL_TIME_KEY,
+    AMRAP_TIME_KEY,
+    FOR_TIME_CAP_KEY,
+];
+const MIN_OPTION_KEY_INDEX: usize = 0;
+const PAGE_STEP: i64 = 10;
+const SHIFT_STEP_MULTIPLIER: i64 = 60;
+
+pub fn get_default() -> HashMap<u8, Option> {
+    HashMap::from([
+        (
+            MODE_KEY,
+            Option {
+                name: DEFAULT_MODE_NAME.to_string(),
+                value: 0,
+                min: 0,
+                max: MODES.len() as u32 - 1,
+            },
+        ),
+        (
+            EXCERCISE_TIME_KEY,
+            Option {
+                name: DEFAULT_EXCERCISE_TIME_NAME.to_string(),
+                value: DEFAULT_EXCERCISE_TIME_VALUE,
+                min: 1,
+                max: 600,
+            },
+        ),
+        (
+            EXCERCISE_QUANTITY_KEY,
+            Option {
+                name: DEFAULT_EXCERCISE_QUANTITY_NAME.to_string(),
+                value: DEFAULT_EXCERCISE_QUANTITY_VALUE,
+                min: 1,
+                max: 20,
+            },
+        ),
+        (
+            EXCERCISE_REST_TIME_KEY,
+            Option {
+                name: DEFAULT_EXCERCISE_REST_TIME_NAME.to_string(),
+                value: DEFAULT_EXCERCISE_REST_TIME_VALUE,
+                min: 0,
+                max: 600,
+            },
+        ),
+        (
+            SET_QUANTITY_KEY,
+            Option {
+                name: DEFAULT_SET_QUANTITY_NAME.to_string(),
+                value: DEFAULT_SET_QUANTITY_VALUE,
+                min: 1,
+                max: 20,
+            },
+        ),
+        (
+            SET_REST_TIME_KEY,
+            Option {
+                name: DEFAULT_SET_REST_TIME_NAME.to_string(),
+                value: DEFAULT_SET_REST_TIME_VALUE,
+                min: 0,
+                max: 600,
+            },
+        ),
+        (
+            PREPARE_TIME_KEY,
+            Option {
+                name: DEFAULT_PREPARE_TIME_NAME.to_string(),
+                value: DEFAULT_PREPARE_TIME_VALUE,
+                min: 0,
+                max: 600,
+            },
+        ),
+        (
+            SET_PREPARE_TIME_KEY,
+            Option {
+                name: DEFAULT_SET_PREPARE_TIME_NAME.to_string(),
+                value: DEFAULT_SET_PREPARE_TIME_VALUE,
+                min: 0,
+                max: 600,
+            },
+        ),
+        (
+            TABATA_WORK_TIME_KEY,
+            Option {
+                name: DEFAULT_TABATA_WORK_TIME_NAME.to_string(),
+                value: DEFAULT_TABATA_WORK_TIME_VALUE,
+                min: 1,
+                max: 600,
+            },
+        ),
+        (
+            TABATA_REST_TIME_KEY,
+            Option {
+                name: DEFAULT_TABATA_REST_TIME_NAME.to_string(),
+                value: DEFAULT_TABATA_REST_TIME_VALUE,
+                min: 0,
+                max: 600,
+            },
+        ),
+        (
+            TABATA_ROUNDS_KEY,
+            Option {
+                name: DEFAULT_TABATA_ROUNDS_NAME.to_string(),
+                value: DEFAULT_TABATA_ROUNDS_VALUE,
+                min: 1,
+                max: 50,
+            },
+        ),
+        (
+            EMOM_INTERVAL_TIME_KEY,
+            Option {
+                name: DEFAULT_EMOM_INTERVAL_TIME_NAME.to_string(),
+                value: DEFAULT_EMOM_INTERVAL_TIME_VALUE,
+                min: 1,
+                max: 600,
+            },
+        ),
+        (
+            EMOM_INTERVALS_KEY,
+            Option {
+                name: DEFAULT_EMOM_INTERVALS_NAME.to_string(),
+                value: DEFAULT_EMOM_INTERVALS_VALUE,
+                min: 1,
+                max: 60,
+            },
+        ),
+        (
+            AMRAP_TIME_KEY,
+            Option {
+                name: DEFAULT_AMRAP_TIME_NAME.to_string(),
+                value: DEFAULT_AMRAP_TIME_VALUE,
+                min: 1,
+                max: 7200,
+            },
+        ),
+        (
+            FOR_TIME_CAP_KEY,
+            Option {
+                name: DEFAULT_FOR_TIME_CAP_NAME.to_string(),
+                value: DEFAULT_FOR_TIME_CAP_VALUE,
+                min: 1,
+                max: 7200,
+            },
+        ),
+    ])
+}
+
+pub fn set_value(
+    user_options: &mut HashMap<u8, Option>,
+    option_key: u8,
+    value: u32,
+) -> Result<(), String> {
+    let option: &mut Option = user_options
+        .get_mut(&option_key)
+        .expect("Failed to find option");
+    if value < option.min || value > option.max {
+        return Err(format!(
+            "{} must be between {} and {}, got {}",
+            option.name, option.min, option.max, value
+        ));
+    }
+    option.value = value;
+    Ok(())
+}
+
+// Moves the value by the step and stops at the limits, stepping from a limit explains it instead
+fn change_value(
+    user_options: &mut HashMap<u8, Option>,
+    option_key: u8,
+    step: i64,
+) -> Result<(), String> {
+    let option: &Option = &user_options[&option_key];
+    let value: i64 = i64::from(option.value) + step;
+    if value < i64::from(option.min) && option.value == option.min {
+        return Err(format!(
+            "{} can not be lower than {}",
+            option.name,
+            get_value_text(option_key, option.min)
+        ));
+    }
+    if value > i64::from(option.max) && option.value == option.max {
+        return Err(format!(
+            "{} can not be higher than {}",
+            option.name,
+            get_value_text(option_key, option.max)
+        ));
+    }
+    let value = value.clamp(i64::from(option.min), i64::from(option.max));
+    set_value(user_options, option_key, value as u32)
+}
+
+// Accepts plain seconds or minutes and seconds separated with a colon, hours work the same way
+fn parse_value(input: &str) -> Result<u32, String> {
+    let get_error = || format!("Invalid value {}, type a number or mm:ss", input);
+    let parts: Vec<&str> = input.split(':').collect();
+    if parts.len() > 3 {
+        return Err(get_error());
+    }
+    parts.iter().try_fold(0, |value: u32, part| {
+        let part: u32 = part.parse().map_err(|_| get_error())?;
+        value
+            .checked_mul(60)
+            .and_then(|value| value.checked_add(part))
+            .ok_or_else(get_error)
+    })
+}
+
+// Stable names used to persist option values
+fn get_option_id(option_key: u8) -> &'static str {
+    match option_key {
+        EXCERCISE_TIME_KEY => "excercise_time",
+        EXCERCISE_QUANTITY_KEY => "excercise_quantity",
+        EXCERCISE_REST_TIME_KEY => "excercise_rest_time",
+        SET_QUANTITY_KEY => "set_quantity",
+        SET_REST_TIME_KEY => "set_rest_time",
+        PREPARE_TIME_KEY => "prepare_time",
+        SET_PREPARE_TIME_KEY => "set_prepare_time",
+        MODE_KEY => "mode",
+        TABATA_WORK_TIME_KEY => "tabata_work_time",
+        TABATA_REST_TIME_KEY => "tabata_rest_time",
+        TABATA_ROUNDS_KEY => "tabata_rounds",
+        EMOM_INTERVAL_TIME_KEY => "emom_interval_time",
+        EMOM_INTERVALS_KEY => "emom_intervals",
+        AMRAP_TIME_KEY => "amrap_time",
+        FOR_TIME_CAP_KEY => "for_time_cap",
+        _ => panic!("Unknown option key {}", option_key),
+    }
+}
+
+pub fn get_values(user_options: &HashMap<u8, Option>) -> BTreeMap<String, u32> {
+    user_options
+        .iter()
+        .map(|(option_key, option)| (get_option_id(*option_key).to_string(), option.value))
+        .collect()
+}
+
+// Unknown and out of range values are skipped, so presets survive changes of the options
+pub fn set_values(user_options: &mut HashMap<u8, Option>, values: &BTreeMap<String, u32>) {
+    let option_keys: Vec<u8> = user_options.keys().copied().collect();
+    for option_key in option_keys {
+        if let Some(value) = values.get(get_option_id(option_key)) {
+            let _ = set_value(user_options, option_key, *value);
+        }
+    }
+}
+
+pub fn get_mode(user_options: &HashMap<u8, Option>) -> Mode {
+    MODES[user_options[&MODE_KEY].value as usize]
+}
+
+pub fn set_mode(user_options: &mut HashMap<u8, Option>, mode: Mode) {
+    let value = MODES
+        .iter()
+        .position(|other| *other == mode)
+        .expect("Failed to find mode");
+    set_value(user_options, MODE_KEY, value as u32).expect("Failed to set mode");
+}
+
+pub fn get_definition(user_options: &HashMap<u8, Option>) -> WorkoutDefinition {
+    let prepare_time: u32 = user_options[&PREPARE_TIME_KEY].value;
+    match get_mode(user_options) {
+        Mode::Intervals => WorkoutDefinition::from_options(
+            user_options[&EXCERCISE_TIME_KEY].value,
+            user_options[&EXCERCISE_QUANTITY_KEY].value,
+            user_options[&EXCERCISE_REST_TIME_KEY].value,
+            user_options[&SET_QUANTITY_KEY].value,
+            user_options[&SET_REST_TIME_KEY].value,
+            prepare_time,
+            user_options[&SET_PREPARE_TIME_KEY].value,
+        ),
+        Mode::Tabata => WorkoutDefinition::tabata(
+            user_options[&TABATA_WORK_TIME_KEY].value,
+            user_options[&TABATA_REST_TIME_KEY].value,
+            user_options[&TABATA_ROUNDS_KEY].value,
+            prepare_time,
+        ),
+        Mode::Emom => WorkoutDefinition::emom(
+            user_options[&EMOM_INTERVAL_TIME_KEY].value,
+            user_options[&EMOM_INTERVALS_KEY].value,
+            prepare_time,
+        ),
+        Mode::Amrap => WorkoutDefinition::amrap(user_options[&AMRAP_TIME_KEY].value, prepare_time),
+        Mode::ForTime => {
+            WorkoutDefinition::for_time(user_options[&FOR_TIME_CAP_KEY].value, prepare_time)
+        }
+    }
+}
+
+fn get_option_keys(mode: Mode) -> &'static [u8] {
+    match mode {
+        Mode::Intervals => &INTERVALS_OPTION_KEYS_LIST,
+        Mode::Tabata => &TABATA_OPTION_KEYS_LIST,
+        Mode::Emom => &EMOM_OPTION_KEYS_LIST,
+        Mode::Amrap => &AMRAP_OPTION_KEYS_LIST,
+        Mode::ForTime => &FOR_TIME_OPTION_KEYS_LIST,
+    }
+}
+
+fn is_time_option(option_key: u8) -> bool {
+    TIME_OPTION_KEYS_LIST.contains(&option_key)
+}
+
+fn get_value_text(option_key: u8, value: u32) -> String {
+    if option_key == MODE_KEY {
+        MODES[value as usize].get_name().to_string()
+    } else if is_time_option(option_key) {
+        get_time_text(value)
+    } else {
+        value.to_string()
+    }
+}
+
+fn get_text_width(text: &str) -> u16 {
+    text.lines()
+        .map(|line| line.chars().count() as u16)
+        .max()
+        .unwrap_or(0)
+}
+
+// Widest option line with the widest value the option can have, so the width does not change
+// while stepping through the values
+fn get_options_width(user_options: &HashMap<u8, Option>, option_keys: &[u8]) -> u16 {
+    option_keys
+        .iter()
+        .map(|option_key| {
+            let option: &Option = &user_options[option_key];
+            let value_width = if *option_key == MODE_KEY {
+                MODES
+                    .iter()
+                    .map(|mode| get_text_width(mode.get_name()))
+                    .max()
+            } else {
+                Some(get_text_width(&get_value_text(*option_key, option.max)))
+            };
+            get_text_width(&option.name) + 2 + value_width.unwrap_or(0)
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+// Width left for the presets and the plan next to content of the given width, zero when it is
+// too narrow for them
+fn get_side_width(width: u16, content_width: u16) -> u16 {
+    let side_width = width.saturating_sub(content_width.max(width * (100 - SIDE_PERCENTAGE) / 100));
+    if side_width >= SIDE_MIN_WIDTH {
+        side_width
+    } else {
+        0
+    }
+}
+
+fn get_plan_length(user_options: &HashMap<u8, Option>) -> usize {
+    Plan::new(&get_definition(user_options)).map_or(0, |plan| plan.phases.len())
+}
+
+// Totals on a single line, for screens too narrow for the plan pane
+fn get_plan_summary(user_options: &HashMap<u8, Option>, theme: &Theme) -> Text<'static> {
+    match Plan::new(&get_definition(user_options)) {
+        Ok(plan) => Text::raw(format!(
+            "Total {} · work {} · rest {}",
+            get_short_time_text(plan.total_time),
+            get_short_time_text(plan.work_time),
+            get_short_time_text(plan.rest_time)
+        )),
+        Err(error) => Text::styled(error, theme.error),
+    }
+}
+
+// Totals and the timeline of the workout the options would start, updated on every change
+fn plan_tui<B: Backend>(
+    frame: &mut Frame<B>,
+    user_options: &HashMap<u8, Option>,
+    state: &State,
+    theme: &Theme,
+    area: Rect,
+) {
+    let block = Block::default()
+        .title("Plan (j/k to scroll)")
+        .borders(Borders::ALL);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(0)].as_ref())
+        .split(block.inner(area));
+    frame.render_widget(block, area);
+    let definition: WorkoutDefinition = get_definition(user_options);
+    let plan: Plan = match Plan::new(&definition) {
+        Ok(plan) => plan,
+        Err(error) => {
+            let text = Text::styled(error, theme.error);
+            frame.render_widget(Paragraph::new(text), chunks[0]);
+            return;
+        }
+    };
+    let summary = format!(
+        "Total: {}\nWork: {}\nRest: {}",
+        get_time_text(plan.total_time),
+        get_time_text(plan.work_time),
+        get_time_text(plan.rest_time)
+    );
+    frame.render_widget(Paragraph::new(summary), chunks[0]);
+    let lines: Vec<Line> = plan
+        .phases
+        .iter()
+        .map(|phase| {
+            // The time goes first, so a narrow pane only cuts off the end of the name
+            let content = format!(
+                "{} · {}",
+                get_short_time_text(phase.time),
+                phase.get_name(&definition)
+            );
+            if phase.state.is_work() {
+                Line::from(content)
+            } else {
+                Line::styled(content, theme.rest)
+            }
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(lines).scroll((state.plan_scroll, 0)),
+        chunks[1],
+    );
+}
+
+fn tui<B: Backend>(
+    frame: &mut Frame<B>,
+    user_options: &mut HashMap<u8, Option>,
+    presets: &Presets,
+    state: &State,
+    theme: &Theme,
+) {
+    let option_keys = get_option_keys(get_mode(user_options));
+    let options_height = option_keys.len() as u16;
+    let size = frame.size();
+    if layout::is_too_small(size, MIN_WIDTH, options_height + 1) {
+        layout::too_small_tui(frame, theme, MIN_WIDTH, options_height + 1);
+        return;
+    }
+    let area = layout::center(size, MAX_WIDTH, MAX_HEIGHT);
+    let options_width = get_options_width(user_options, option_keys);
+    let header_width = get_text_width(USER_OPTIONS_HEADER).max(get_text_width(PRESETS_HEADER));
+    // The full header is given up before the presets and the plan
+    let mut side_width = get_side_width(area.width, options_width.max(header_width));
+    if side_width == 0 {
+        side_width = get_side_width(area.width, options_width);
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Length(area.width - side_width),
+                Constraint::Length(side_width),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+    let default_style: Style = Style::default();
+    let active_style: Style = theme.active;
+    let is_full_header = area.height >= HEADER_HEIGHT + options_height + MESSAGE_HEIGHT
+        && chunks[0].width >= header_width;
+    let mut text = if is_full_header {
+        let mut text = Text::styled(
+            COMMON_HEADER,
+            Style::default().add_modifier(Modifier::ITALIC),
+        );
+        text.extend(Text::raw(USER_OPTIONS_HEADER));
+        text.extend(Text::raw(PRESETS_HEADER));
+        text
+    } else {
+        Text::styled(
+            SHORT_HEADER,
+            Style::default().add_modifier(Modifier::ITALIC),
+        )
+    };
+    for (index, option_key) in option_keys.iter().enumerate() {
+        let is_active: bool = index == state.active_index && !state.is_presets_active;
+        let value_text: String = match &state.input {
+            Some(input) if is_active => format!("{}_", input),
+            _ => get_value_text(*option_key, user_options[option_key].value),
+        };
+        let content: String = format!("{}: {}", user_options[option_key].name, value_text);
+        if is_active {
+            text.extend(Text::styled(content, active_style));
+        } else {
+            text.extend(Text::styled(content, default_style));
+        }
+    }
+    if let Some(name) = &state.preset_name {
+        text.extend(Text::raw(format!(
+            "\nPreset name (Enter to save, Esc to cancel): {}_",
+            name
+        )));
+    }
+    if !state.message.is_empty() {
+        text.extend(Text::styled(format!("\n{}", state.message), theme.error));
+    }
+    if side_width == 0 {
+        text.extend(Text::raw("\n"));
+        text.extend(get_plan_summary(user_options, theme));
+    }
+    // Only the preset name and the messages can be wider than the options
+    frame.render_widget(
+        Paragraph::new(text)
+            .block(Block::new())
+            .wrap(Wrap { trim: false }),
+        chunks[0],
+    );
+    if side_width == 0 {
+        return;
+    }
+
+    let items: Vec<ListItem> = presets
+        .presets
+        .iter()
+        .enumerate()
+        .map(|(index, preset)| {
+            if index == state.preset_index && state.is_presets_active {
+                ListItem::new(preset.name.clone()).style(active_style)
+            } else {
+                ListItem::new(preset.name.clone()).style(default_style)
+            }
+        })
+        .collect();
+    let side_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)].as_ref())
+        .split(chunks[1]);
+    let list = List::new(items).block(Block::default().title("Presets").borders(Borders::ALL));
+    frame.render_widget(list, side_chunks[0]);
+    plan_tui(frame, user_options, state, theme, side_chunks[1]);
+}
+
+// Handles keys while typing the name of a new preset
+fn on_preset_name_key(
+    key_code: KeyCode,
+    user_options: &HashMap<u8, Option>,
+    presets: &mut Presets,
+    state: &mut State,
+) {
+    let name: &mut String = state
+        .preset_name
+        .as_mut()
+        .expect("Failed to get preset name");
+    match key_code {
+        KeyCode::Char(character) => name.push(character),
+        KeyCode::Backspace => {
+            name.pop();
+        }
+        KeyCode::Esc => state.preset_name = None,
+        KeyCode::Enter if !name.trim().is_empty() => {
+            presets.add(name.trim().to_string(), get_values(user_options));
+            state.preset_name = None;
+            save_presets(presets, state);
+        }
+        _ => {}
+    }
+}
+
+// Handles keys while typing a value for the active option
+fn on_value_key(
+    key_code: KeyCode,
+    user_options: &mut HashMap<u8, Option>,
+    option_key: u8,
+    state: &mut State,
+) {
+    let input: &mut String = state.input.as_mut().expect("Failed to get value input");
+    match key_code {
+        KeyCode::Char(character)
+            if character.is_ascii_digit() || (character == ':' && is_time_option(option_key)) =>
+        {
+            input.push(character)
+        }
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Esc => state.input = None,
+        KeyCode::Enter => {
+            let result =
+                parse_value(input).and_then(|value| set_value(user_options, option_key, value));
+            state.message = match result {
+                Ok(()) => String::new(),
+                Err(error) => error,
+            };
+            state.input = None;
+        }
+        _ => {}
+    }
+}
+
+fn on_presets_key(
+    key_code: KeyCode,
+    user_options: &mut HashMap<u8, Option>,
+    presets: &mut Presets,
+    state: &mut State,
+) {
+    match key_code {
+        KeyCode::Down | KeyCode::Char('s') if state.preset_index + 1 < presets.presets.len() => {
+            state.preset_index += 1;
+        }
+        KeyCode::Up | KeyCode::Char('w') if state.preset_index > 0 => {
+            state.preset_index -= 1;
+        }
+        KeyCode::Enter => {
+            if let Some(preset) = presets.presets.get(state.preset_index) {
+                set_values(user_options, &preset.values);
+                state.active_index = MIN_OPTION_KEY_INDEX;
+                state.is_presets_active = false;
+            }
+        }
+        KeyCode::Char('x') if state.preset_index < presets.presets.len() => {
+            presets.remove(state.preset_index);
+            state.preset_index = state.preset_index.saturating_sub(1);
+            save_presets(presets, state);
+        }
+        _ => {}
+    }
+}
+
+fn save_presets(presets: &Presets, state: &mut State) {
+    state.message = match presets.save() {
+        Ok(()) => String::new(),
+        Err(error) => format!("Failed to save presets: {}", error),
+    };
+}
+
+fn get_user_options<B: Backend>(
+    terminal: &mut Terminal<B>,
+    user_options: &mut HashMap<u8, Option>,
+    presets: &mut Presets,
+    theme: &Theme,
+    input: &mut impl Input,
+) -> io::Result<u8> {
+    let mut state = State {
+        active_index: MIN_OPTION_KEY_INDEX,
+        preset_index: 0,
+        is_presets_active: false,
+        preset_name: None,
+        input: None,
+        plan_scroll: 0,
+        message: String::new(),
+    };
+    let mut step: i64 = 0;
+    let mut option_key: u8;
+    let mut option_keys: &[u8];
+    loop {
+        terminal
+            .draw(|frame: &mut Frame<'_, B>| tui(frame, user_options, presets, &state, theme))?;
+        // Nothing changes on its own, so the screen waits for the next event
+        let key = match input.read(None)? {
+            InputEvent::Key(key) => key,
+            InputEvent::Interrupt => return Ok(1),
+            InputEvent::Suspend => {
+                input.suspend(terminal)?;
+                continue;
+            }
+            InputEvent::Tick | InputEvent::Resize => continue,
+        };
+        option_keys = get_option_keys(get_mode(user_options));
+        option_key = option_keys[state.active_index];
+        if state.preset_name.is_some() {
+            on_preset_name_key(key.code, user_options, presets, &mut state);
+        } else if state.input.is_some() {
+            on_value_key(key.code, user_options, option_key, &mut state);
+        } else if KeyCode::Char('q') == key.code {
+            return Ok(1);
+        } else if KeyCode::Char('t') == key.code {
+            // Statistics are informative only, a broken history shows up as empty
+            let sessions = history::load().unwrap_or_default();
+            stats::run(
+                terminal,
+                &Stats::new(&sessions, Local::now().date_naive()),
+                theme,
+                input,
+            )?;
+        } else if KeyCode::Tab == key.code {
+            state.is_presets_active = !state.is_presets_active;
+        } else if KeyCode::Char('p') == key.code {
+            state.preset_name = Some(String::new());
+        } else if KeyCode::Char('j') == key.code {
+            let last_line = get_plan_length(user_options).saturating_sub(1) as u16;
+            state.plan_scroll = (state.plan_scroll + 1).min(last_line);
+        } else if KeyCode::Char('k') == key.code {
+            state.plan_scroll = state.plan_scroll.saturating_sub(1);
+        } else if state.is_presets_active {
+            on_presets_key(key.code, user_options, presets, &mut state);
+        } else if KeyCode::Enter == key.code {
+            // Options are checked one by one, the whole workout only once it is started
+            match get_definition(user_options).validate() {
+                Ok(()) => return Ok(0),
+                Err(error) => state.message = error,
+            }
+        } else if (KeyCode::Down == key.code || KeyCode::Char('s') == key.code)
+            && state.active_index < option_keys.len() - 1
+        {
+            state.active_index += 1;
+        } else if (KeyCode::Up == key.code || KeyCode::Char('w') == key.code)
+            && state.active_index > MIN_OPTION_KEY_INDEX
+        {
+            state.active_index -= 1;
+        } else if let KeyCode::Char(character @ '0'..='9') = key.code {
+            if option_key != MODE_KEY {
+                state.input = Some(character.to_string());
+            }
+        } else if let KeyCode::Left | KeyCode::Char('a') | KeyCode::Char('A') = key.code {
+            step = -1;
+        } else if let KeyCode::Right | KeyCode::Char('d') | KeyCode::Char('D') = key.code {
+            step = 1;
+        } else if KeyCode::PageDown == key.code {
+            step = -PAGE_STEP;
+        } else if KeyCode::PageUp == key.code {
+            step = PAGE_STEP;
+        } else if KeyCode::Home == key.code {
+            step = i64::from(user_options[&option_key].min)
+                - i64::from(user_options[&option_key].value);
+        } else if KeyCode::End == key.code {
+            step = i64::from(user_options[&option_key].max)
+                - i64::from(user_options[&option_key].value);
+        }
+        if key.modifiers.contains(KeyModifiers::SHIFT) {
+            step *= SHIFT_STEP_MULTIPLIER;
+        }
+        if step != 0 {
+            state.message = match change_value(user_options, option_key, step) {
+                Ok(()) => String::new(),
+                Err(error) => error,
+            };
+            step = 0;
+        }
+    }
+}
+
+pub fn get<B: Backend>(
+    terminal: &mut Terminal<B>,
+    mut user_options: HashMap<u8, Option>,
+    presets: &mut Presets,
+    theme: &Theme,
+    input: &mut impl Input,
+) -> (u8, HashMap<u8, Option>) {
+    let status: u8 = get_user_options(terminal, &mut user_options, presets, theme, input)
+        .expect("Failed to get user options");
+    (status, user_options)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{
+        change_value, get_default, parse_value, set_value, tui, State, EXCERCISE_QUANTITY_KEY,
+        EXCERCISE_REST_TIME_KEY, MODE_KEY, SET_REST_TIME_KEY, TABATA_REST_TIME_KEY,
+    };
+    use crate::{
+        presets::Presets,
+        snapshot::{self, SIZES},
+        theme::Theme,
+    };
+
+    fn get_state(is_presets_active: bool) -> State {
+        State {
+            active_index: 1,
+            preset_index: 1,
+            is_presets_active,
+            preset_name: None,
+            input: None,
+            plan_scroll: 0,
+            message: String::new(),
+        }
+    }
+
+    fn get_presets() -> Presets {
+        let mut presets = Presets::default();
+        presets.add(String::from("Legs"), BTreeMap::new());
+        presets.add(String::from("Core"), BTreeMap::new());
+        presets
+    }
+
+    #[test]
+    fn options_screens_match_snapshots() {
+        let presets = get_presets();
+        let mut user_options = get_default();
+        for (width, height) in SIZES {
+            let buffer = snapshot::render(width, height, |frame| {
+                tui(
+                    frame,
+                    &mut user_options,
+                    &presets,
+                    &get_state(false),
+                    &Theme::default(),
+                )
+            });
+            snapshot::assert_snapshot(&format!("options_{}x{}", width, height), &buffer);
+        }
+        let mut state = get_state(true);
+        state.plan_scroll = 4;
+        state.message = String::from("Failed to save presets: No config directory");
+        let buffer = snapshot::render(80, 24, |frame| {
+            tui(
+                frame,
+                &mut user_options,
+                &presets,
+                &state,
+                &Theme::default(),
+            )
+        });
+        snapshot::assert_snapshot("options_presets_active_80x24", &buffer);
+        set_value(&mut user_options, MODE_KEY, 1).unwrap();
+        let mut state = get_state(false);
+        state.preset_name = Some(String::from("Tab"));
+        state.input = Some(String::from("0:3"));
+        let buffer = snapshot::render(80, 24, |frame| {
+            tui(
+                frame,
+                &mut user_options,
+                &presets,
+                &state,
+                &Theme::default(),
+            )
+        });
+        snapshot::assert_snapshot("options_tabata_preset_name_80x24", &buffer);
+    }
+
+    #[test]
+    fn small_and_large_options_screens_match_snapshots() {
+        let presets = get_presets();
+        let mut user_options = get_default();
+        // Too small, without the presets and the plan, centered on a big monitor
+        for (width, height) in [(36, 8), (50, 14), (200, 60)] {
+            let buffer = snapshot::render(width, height, |frame| {
+                tui(
+                    frame,
+                    &mut user_options,
+                    &presets,
+                    &get_state(false),
+                    &Theme::default(),
+                )
+            });
+            snapshot::assert_snapshot(&format!("options_{}x{}", width, height), &buffer);
+        }
+    }
+
+    #[test]
+    fn stepping_past_limits_explains_them() {
+        let mut user_options = get_default();
+        assert_eq!(
+            change_value(&mut user_options, MODE_KEY, -1),
+            Err(String::from("Workout mode can not be lower than Intervals"))
+        );
+        set_value(&mut user_options, EXCERCISE_QUANTITY_KEY, 1).unwrap();
+        assert_eq!(
+            change_value(&mut user_options, EXCERCISE_QUANTITY_KEY, -1),
+            Err(String::from(
+                "Number of excercises per onse set can not be lower than 1"
+            ))
+        );
+        assert_eq!(user_options[&EXCERCISE_QUANTITY_KEY].value, 1);
+        assert_eq!(
+            change_value(&mut user_options, EXCERCISE_QUANTITY_KEY, 1),
+            Ok(())
+        );
+        assert_eq!(user_options[&EXCERCISE_QUANTITY_KEY].value, 2);
+        // Rests can be left out, like in a circuit
+        set_value(&mut user_options, EXCERCISE_REST_TIME_KEY, 5).unwrap();
+        assert_eq!(
+            change_value(&mut user_options, EXCERCISE_REST_TIME_KEY, -10),
+            Ok(())
+        );
+        assert_eq!(user_options[&EXCERCISE_REST_TIME_KEY].value, 0);
+        assert_eq!(
+            change_value(&mut user_options, EXCERCISE_REST_TIME_KEY, -1),
+            Err(String::from(
+                "Rest time between excercises can not be lower than 00:00:00"
+            ))
+        );
+        assert_eq!(set_value(&mut user_options, SET_REST_TIME_KEY, 0), Ok(()));
+        assert_eq!(
+            set_value(&mut user_options, TABATA_REST_TIME_KEY, 0),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn typed_values_accept_seconds_and_minutes() {
+        assert_eq!(parse_value("300"), Ok(300));
+        assert_eq!(parse_value("5:00"), Ok(300));
+        assert_eq!(parse_value("1:02:03"), Ok(3723));
+        assert_eq!(
+            parse_value("5:"),
+            Err(String::from("Invalid value 5:, type a number or mm:ss"))
+        );
+        assert!(parse_value("1:1:1:1").is_err());
+        let mut user_options = get_default();
+        assert_eq!(
+            change_value(&mut user_options, SET_REST_TIME_KEY, 600),
+            Ok(())
+        );
+        assert_eq!(user_options[&SET_REST_TIME_KEY].value, 600);
+        assert_eq!(
+            change_value(&mut user_options, SET_REST_TIME_KEY, 60),
+            Err(String::from(
+                "Rest time between sets can not be higher than 00:10:00"
+            ))
+        );
+    }
+}
@@ -0,0 +1,69 @@
+//! Time sources for [`Timer::advance_to`](crate::timer::Timer::advance_to), the real monotonic
+//! clock for apps and a manual one for simulations and tests.
+
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+/// Monotonic time since the clock was started.
+pub trait Clock {
+    /// Time since the clock was started.
+    fn now(&self) -> Duration;
+}
+
+/// Clock measuring the real time since it was created.
+pub struct MonotonicClock {
+    start: Instant,
+}
+
+/// Clock that only moves when told to, clones share the same time.
+#[derive(Clone, Default)]
+pub struct ManualClock {
+    now: Rc<Cell<Duration>>,
+}
+
+impl MonotonicClock {
+    /// Clock starting at zero now.
+    pub fn new() -> MonotonicClock {
+        MonotonicClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for MonotonicClock {
+    fn default() -> MonotonicClock {
+        MonotonicClock::new()
+    }
+}
+
+impl Clock for MonotonicClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+impl ManualClock {
+    /// Clock standing at zero.
+    pub fn new() -> ManualClock {
+        ManualClock::default()
+    }
+
+    /// Moves the clock forward by `elapsed`.
+    pub fn advance(&self, elapsed: Duration) {
+        self.now.set(self.now.get() + elapsed);
+    }
+
+    /// Moves the clock to `now`, a clock never goes back so earlier times are ignored.
+    pub fn set(&self, now: Duration) {
+        self.now.set(self.now.get().max(now));
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
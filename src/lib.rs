@@ -0,0 +1,26 @@
+//! Interval workout engine behind the `workout_timer` terminal app.
+//!
+//! A [`workout::WorkoutDefinition`] describes the sets and excercises, a [`timer::Timer`] built
+//! from it is moved forward with [`timer::Timer::advance`] and returns the cues to announce.
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use workout_timer::{
+//!     timer::{Cue, Timer, TimerState},
+//!     workout::WorkoutDefinition,
+//! };
+//!
+//! let mut timer = Timer::new(WorkoutDefinition::tabata(20, 10, 8, 0)).unwrap();
+//! assert_eq!(timer.get_state(), TimerState::Set);
+//! let cues = timer.advance(Duration::from_secs(20));
+//! assert_eq!(cues.last(), Some(&Cue::RestStart));
+//! assert_eq!(timer.get_remaining_time(), 10);
+//! ```
+
+#![warn(missing_docs)]
+
+pub mod clock;
+pub mod plan;
+pub mod timer;
+pub mod workout;
@@ -0,0 +1,132 @@
+//! Workout plan, the list of phases a [`Timer`](crate::timer::Timer) goes through, available
+//! before the workout starts.
+
+use crate::{
+    timer::{Phase, TimerState},
+    workout::WorkoutDefinition,
+};
+
+/// Phases of a workout with their total times in seconds.
+pub struct Plan {
+    /// Phases in the order they are done, phases without any time are left out.
+    pub phases: Vec<Phase>,
+    /// Seconds of the whole workout.
+    pub total_time: u32,
+    /// Seconds of warm up, excercises and cool down.
+    pub work_time: u32,
+    /// Seconds of rests and get ready countdowns.
+    pub rest_time: u32,
+}
+
+impl Plan {
+    /// Lists the phases of the workout in order, fails when the definition does not pass
+    /// [`WorkoutDefinition::validate`].
+    pub fn new(definition: &WorkoutDefinition) -> Result<Plan, String> {
+        definition.validate()?;
+        let mut phases: Vec<Phase> = Vec::new();
+        for (item_index, item_definition) in definition.warm_up.iter().enumerate() {
+            phases.push(Phase {
+                state: TimerState::WarmUp,
+                set_index: 0,
+                excercise_index: item_index,
+                time: item_definition.time,
+            });
+        }
+        for (set_index, set_definition) in definition.sets.iter().enumerate() {
+            if set_index > 0 {
+                phases.push(Phase {
+                    state: TimerState::SetRest,
+                    set_index: set_index - 1,
+                    excercise_index: definition.sets[set_index - 1].excercises.len() - 1,
+                    time: definition.sets[set_index - 1].rest_time,
+                });
+            }
+            let prepare_time = if set_index == 0 {
+                definition.prepare_time
+            } else {
+                definition.set_prepare_time
+            };
+            if prepare_time > 0 {
+                phases.push(Phase {
+                    state: TimerState::Prepare,
+                    set_index,
+                    excercise_index: 0,
+                    time: prepare_time,
+                });
+            }
+            for (excercise_index, excercise_definition) in
+                set_definition.excercises.iter().enumerate()
+            {
+                if excercise_index > 0 {
+                    phases.push(Phase {
+                        state: TimerState::ExcerciseRest,
+                        set_index,
+                        excercise_index: excercise_index - 1,
+                        time: set_definition.excercises[excercise_index - 1].rest_time,
+                    });
+                }
+                phases.push(Phase {
+                    state: TimerState::Set,
+                    set_index,
+                    excercise_index,
+                    time: excercise_definition.time,
+                });
+            }
+        }
+        for (item_index, item_definition) in definition.cool_down.iter().enumerate() {
+            phases.push(Phase {
+                state: TimerState::CoolDown,
+                set_index: definition.sets.len() - 1,
+                excercise_index: item_index,
+                time: item_definition.time,
+            });
+        }
+        // Rests and countdowns can be disabled with zero time
+        phases.retain(|phase| phase.time > 0);
+        let work_time: u32 = phases
+            .iter()
+            .filter(|phase| phase.state.is_work())
+            .map(|phase| phase.time)
+            .sum();
+        let total_time: u32 = phases.iter().map(|phase| phase.time).sum();
+        Ok(Plan {
+            phases,
+            total_time,
+            work_time,
+            rest_time: total_time - work_time,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Plan;
+    use crate::workout::WorkoutDefinition;
+
+    #[test]
+    fn plan_lists_phases_with_total_times() {
+        let definition = WorkoutDefinition::from_options(45, 2, 15, 2, 60, 10, 0);
+        let plan = Plan::new(&definition).unwrap();
+        let names: Vec<String> = plan
+            .phases
+            .iter()
+            .map(|phase| format!("{} · {}", phase.get_name(&definition), phase.time))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "Get ready · Set 1 · 10",
+                "Set 1 · Excercise 1 · 45",
+                "Rest · 15",
+                "Set 1 · Excercise 2 · 45",
+                "Set rest · 60",
+                "Set 2 · Excercise 1 · 45",
+                "Rest · 15",
+                "Set 2 · Excercise 2 · 45",
+            ]
+        );
+        assert_eq!(plan.total_time, 280);
+        assert_eq!(plan.work_time, 180);
+        assert_eq!(plan.rest_time, 100);
+    }
+}
@@ -1,310 +1,830 @@
-use std::{
-    io,
-    time::{Duration, Instant},
-};
+//! Interval timer state machine, the [`Timer`] is built from a [`WorkoutDefinition`] and moved
+//! forward with [`Timer::advance_to`], which returns the [`Cue`]s that should be announced.
+
+use std::time::Duration;
 
-use crossterm::event::{self, Event, KeyCode};
-use ratatui::{
-    prelude::{Backend, Constraint, Direction, Layout},
-    style::{Color, Modifier, Style},
-    text::Text,
-    widgets::{Block, Borders, Gauge, Paragraph},
-    Frame, Terminal,
+use crate::{
+    plan::Plan,
+    workout::{Mode, WorkoutDefinition},
 };
 
-const TICK_RATE: Duration = Duration::from_secs(1);
-const COMMON_HEADER: &str = "Workout Timer developed by dev-michal-skiba\n\n";
-const TIMER_HEADER: &str = "Type q to exit\n\n";
+const TIME_STEP: u32 = 10;
+const COUNTDOWN_TIME: u32 = 3;
+
+/// Moment of the workout worth announcing to the user.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Cue {
+    /// Work or warm up item started.
+    WorkStart,
+    /// Rest between excercises started.
+    RestStart,
+    /// Set finished, a set rest or the cool down started.
+    SetDone,
+    /// Whole workout finished.
+    WorkoutDone,
+    /// One of the last seconds of the current phase.
+    Countdown,
+}
 
-#[derive(PartialEq, Eq, Hash)]
-enum TimerState {
+/// Kind of the phase the timer is in.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum TimerState {
+    /// Warm up item before the first set.
+    WarmUp,
+    /// Get ready countdown before a set.
+    Prepare,
+    /// Excercise of a set.
     Set,
+    /// Rest between sets.
     SetRest,
+    /// Rest between excercises of a set.
     ExcerciseRest,
+    /// Cool down item after the last set.
+    CoolDown,
+}
+
+impl TimerState {
+    /// Warm up, excercises and cool down are work, get ready countdowns and rests are not.
+    pub fn is_work(&self) -> bool {
+        match self {
+            TimerState::WarmUp | TimerState::Set | TimerState::CoolDown => true,
+            TimerState::Prepare | TimerState::ExcerciseRest | TimerState::SetRest => false,
+        }
+    }
+}
+
+/// Elapsed and total seconds of a timer with their `hh:mm:ss` texts.
+///
+/// The `ratio` of the timers using it is calculated from the precise elapsed time, so gauges
+/// move smoothly between whole seconds.
+#[derive(Debug, Clone)]
+pub struct Time {
+    /// Whole seconds elapsed.
+    pub current: u32,
+    /// Elapsed seconds as `hh:mm:ss`.
+    pub current_text: String,
+    /// Total seconds.
+    pub max: u32,
+    /// Total seconds as `hh:mm:ss`.
+    pub max_text: String,
+    /// Precise elapsed time, `current` holds its whole seconds.
+    pub elapsed: Duration,
+}
+
+/// Progress of the whole workout.
+#[derive(Debug, Clone)]
+pub struct Workout {
+    /// Elapsed part of the time in percents.
+    pub progress: u16,
+    /// Elapsed part of the time between 0 and 1.
+    pub ratio: f64,
+    /// Whether all the time has elapsed.
+    pub is_finished: bool,
+    /// Elapsed and total time.
+    pub time: Time,
 }
 
-struct Time {
-    current: u32,
-    current_text: String,
-    max: u32,
-    max_text: String,
+/// Progress of the current set, `current` is the one-based set number.
+#[derive(Debug, Clone)]
+pub struct Set {
+    /// Elapsed part of the time in percents.
+    pub progress: u16,
+    /// Elapsed part of the time between 0 and 1.
+    pub ratio: f64,
+    /// Whether all the time has elapsed.
+    pub is_finished: bool,
+    /// Elapsed and total time.
+    pub time: Time,
+    /// One-based number of the current set.
+    pub current: u32,
+    /// Number of sets in the workout.
+    pub quantity: u32,
 }
 
-struct Workout {
-    progress: u16,
-    is_finished: bool,
-    time: Time,
+/// Progress of the current excercise, also used for warm up and cool down items.
+#[derive(Debug, Clone)]
+pub struct Excercise {
+    /// Name from the definition.
+    pub name: String,
+    /// Elapsed part of the time in percents.
+    pub progress: u16,
+    /// Elapsed part of the time between 0 and 1.
+    pub ratio: f64,
+    /// Whether all the time has elapsed.
+    pub is_finished: bool,
+    /// Elapsed and total time.
+    pub time: Time,
+    /// One-based number of the excercise in its set, or of the warm up or cool down item.
+    pub current: u32,
+    /// Number of excercises in the set, or of warm up or cool down items.
+    pub quantity: u32,
 }
 
-struct Set {
-    progress: u16,
-    is_finished: bool,
-    time: Time,
-    current: u32,
-    quantity: u32,
+/// Progress of the current rest or get ready countdown.
+#[derive(Debug, Clone)]
+pub struct Rest {
+    /// Elapsed part of the time in percents.
+    pub progress: u16,
+    /// Elapsed part of the time between 0 and 1.
+    pub ratio: f64,
+    /// Whether all the time has elapsed.
+    pub is_finished: bool,
+    /// Elapsed and total time.
+    pub time: Time,
 }
 
-struct Excercise {
-    progress: u16,
-    is_finished: bool,
-    time: Time,
-    current: u32,
-    quantity: u32,
+/// Single step of the workout.
+///
+/// The excercise and set indexes point at the definition the phase belongs to, rests belong to
+/// the excercise or set they follow and get ready countdowns belong to the set they precede, warm
+/// up and cool down use the excercise index for their items.
+#[derive(Debug, Clone)]
+pub struct Phase {
+    /// Kind of the phase.
+    pub state: TimerState,
+    /// Index of the set in the definition.
+    pub set_index: usize,
+    /// Index of the excercise in its set, or of the warm up or cool down item.
+    pub excercise_index: usize,
+    /// Length of the phase in seconds.
+    pub time: u32,
 }
 
-struct Rest {
-    progress: u16,
-    is_finished: bool,
-    time: Time,
+impl Phase {
+    /// Short name of the phase, like `Set 1 · Excercise 2`. Tabata, EMOM, AMRAP and for time
+    /// workouts have no sets to choose, so their names follow the rounds and intervals instead.
+    pub fn get_name(&self, definition: &WorkoutDefinition) -> String {
+        let excercises = &definition.sets[self.set_index].excercises;
+        match (definition.mode, self.state) {
+            (_, TimerState::WarmUp) => format!(
+                "Warm up · {}",
+                definition.warm_up[self.excercise_index].name
+            ),
+            (_, TimerState::CoolDown) => format!(
+                "Cool down · {}",
+                definition.cool_down[self.excercise_index].name
+            ),
+            (Mode::Intervals, TimerState::Prepare) => {
+                format!("Get ready · Set {}", self.set_index + 1)
+            }
+            (_, TimerState::Prepare) => String::from("Get ready"),
+            (Mode::Intervals, TimerState::Set) => format!(
+                "Set {} · {} {}",
+                self.set_index + 1,
+                excercises[self.excercise_index].name,
+                self.excercise_index + 1
+            ),
+            (Mode::Tabata, TimerState::Set) => format!(
+                "{} {}/{}",
+                excercises[self.excercise_index].name,
+                self.excercise_index + 1,
+                excercises.len()
+            ),
+            (Mode::Tabata, TimerState::ExcerciseRest) => format!(
+                "Tabata Rest {}/{}",
+                self.excercise_index + 1,
+                excercises.len()
+            ),
+            (Mode::Emom, TimerState::Set) => format!(
+                "EMOM {} {}/{}",
+                excercises[self.excercise_index].name,
+                self.set_index + 1,
+                definition.sets.len()
+            ),
+            (Mode::Amrap | Mode::ForTime, TimerState::Set) => {
+                excercises[self.excercise_index].name.clone()
+            }
+            (_, TimerState::ExcerciseRest) => String::from("Rest"),
+            (_, TimerState::SetRest) => String::from("Set rest"),
+        }
+    }
 }
 
+/// Workout timer without any terminal dependency.
+///
+/// The timer only moves when [`Timer::advance_to`] or [`Timer::advance`] is called, so the
+/// caller decides where the time comes from.
 pub struct Timer {
+    definition: WorkoutDefinition,
+    phases: Vec<Phase>,
+    phase_index: usize,
+    phase_elapsed: Duration,
+    // Last monotonic time passed to advance_to and the part of it spent paused
+    clock: Duration,
+    paused_time: Duration,
+    is_paused: bool,
+    rounds: u32,
+    // Seconds actually spent working and resting, counted on every whole second
+    work_time: u32,
+    rest_time: u32,
     workout: Workout,
+    warm_up: Excercise,
+    prepare: Rest,
     set: Set,
     set_rest: Rest,
     excercise: Excercise,
     excercise_rest: Rest,
+    cool_down: Excercise,
     state: TimerState,
 }
 
+impl Time {
+    fn new() -> Time {
+        Time {
+            current: 0,
+            current_text: get_time_text(0),
+            max: 0,
+            max_text: get_time_text(0),
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    fn update(&mut self, elapsed: Duration, max: u32) {
+        self.current = elapsed.as_secs() as u32;
+        self.current_text = get_time_text(self.current);
+        self.max = max;
+        self.max_text = get_time_text(max);
+        self.elapsed = elapsed;
+    }
+
+    /// Elapsed time with tenths of a second, like `00:00:03.4`.
+    pub fn get_precise_text(&self) -> String {
+        format!(
+            "{}.{}",
+            self.current_text,
+            self.elapsed.subsec_millis() / 100
+        )
+    }
+
+    // A phase without any time is done as soon as it starts
+    fn get_progress(&self) -> u16 {
+        if self.max == 0 {
+            return 100;
+        }
+        let progress = self.elapsed.as_millis() * 100 / (u128::from(self.max) * 1000);
+        u16::try_from(progress.min(100)).expect("Failed to calculate time progress")
+    }
+
+    fn get_ratio(&self) -> f64 {
+        if self.max == 0 {
+            return 1.0;
+        }
+        (self.elapsed.as_secs_f64() / f64::from(self.max)).min(1.0)
+    }
+}
+
 impl Workout {
-    fn new(max_time: u32) -> Workout {
-        let max_time_text: String = get_time_text(max_time);
+    fn new() -> Workout {
         Workout {
             progress: 0,
+            ratio: 0.0,
             is_finished: false,
-            time: Time {
-                current: 0,
-                current_text: String::from("00:00:00"),
-                max: max_time,
-                max_text: max_time_text,
-            },
+            time: Time::new(),
         }
     }
 
-    fn on_tick(&mut self) {
-        if self.is_finished == false {
-            self.time.current += 1;
-            self.time.current_text = get_time_text(self.time.current);
-            self.progress = u16::try_from(self.time.current * 100 / self.time.max)
-                .expect("Failed to calculate full time progress");
-            if self.time.current == self.time.max {
-                self.is_finished = true;
-            }
-        }
+    fn update(&mut self, elapsed: Duration, max: u32) {
+        self.time.update(elapsed, max);
+        self.progress = self.time.get_progress();
+        self.ratio = self.time.get_ratio();
+        self.is_finished = self.time.current >= max;
     }
 
-    fn get_label(&mut self) -> String {
+    /// Elapsed and total time, like `00:01:30/00:30:00`.
+    pub fn get_label(&self) -> String {
         format!("{}/{}", self.time.current_text, self.time.max_text)
     }
 }
 
 impl Set {
-    fn new(max_time: u32, quantity: u32) -> Set {
+    fn new(quantity: u32) -> Set {
         Set {
             progress: 0,
+            ratio: 0.0,
             is_finished: false,
-            time: Time {
-                current: 0,
-                current_text: get_time_text(0),
-                max: max_time,
-                max_text: get_time_text(max_time),
-            },
+            time: Time::new(),
             current: 1,
-            quantity: quantity,
+            quantity,
         }
     }
 
-    fn on_tick(&mut self, increment: u32) {
-        if self.is_finished == false {
-            self.time.current += increment;
-            self.time.current_text = get_time_text(self.time.current);
-            self.progress = u16::try_from(self.time.current * 100 / self.time.max)
-                .expect("Failed to calculate full time progress");
-            if self.time.current == self.time.max {
-                self.is_finished = true;
-            }
-        }
-    }
-
-    fn clear(&mut self) {
-        self.is_finished = false;
-        self.progress = 0;
-        self.time.current = 0;
-        self.time.current_text = get_time_text(0);
-    }
-
-    fn increment(&mut self) {
-        self.current += 1;
+    fn update(&mut self, elapsed: Duration, max: u32) {
+        self.time.update(elapsed, max);
+        self.progress = self.time.get_progress();
+        self.ratio = self.time.get_ratio();
+        self.is_finished = self.time.current >= max;
     }
 
-    fn get_title(&mut self) -> String {
+    /// Gauge title with the set number, like `Set 1/3 Timer`.
+    pub fn get_title(&self) -> String {
         format!("Set {}/{} Timer", self.current, self.quantity)
     }
 
-    fn get_label(&mut self) -> String {
+    /// Elapsed and total time, like `00:01:30/00:30:00`.
+    pub fn get_label(&self) -> String {
         format!("{}/{}", self.time.current_text, self.time.max_text)
     }
 }
 
 impl Excercise {
-    fn new(max_time: u32, quantity: u32) -> Excercise {
+    fn new() -> Excercise {
         Excercise {
+            name: String::new(),
             progress: 0,
+            ratio: 0.0,
             is_finished: false,
-            time: Time {
-                current: 0,
-                current_text: get_time_text(0),
-                max: max_time,
-                max_text: get_time_text(max_time),
-            },
+            time: Time::new(),
             current: 1,
-            quantity: quantity,
+            quantity: 1,
         }
     }
 
-    fn on_tick(&mut self, increment: u32) {
-        if self.is_finished == false {
-            self.time.current += increment;
-            self.time.current_text = get_time_text(self.time.current);
-            self.progress = u16::try_from(self.time.current * 100 / self.time.max)
-                .expect("Failed to calculate full time progress");
-            if self.time.current == self.time.max {
-                self.is_finished = true;
-            }
-        }
+    fn update(&mut self, elapsed: Duration, max: u32) {
+        self.time.update(elapsed, max);
+        self.progress = self.time.get_progress();
+        self.ratio = self.time.get_ratio();
+        self.is_finished = self.time.current >= max;
     }
 
-    fn clear(&mut self) {
-        self.is_finished = false;
-        self.progress = 0;
-        self.time.current = 0;
-        self.time.current_text = get_time_text(0);
+    /// Gauge title with the name and number, like `Excercise 2/10 Timer`.
+    pub fn get_title(&self) -> String {
+        format!("{} {}/{} Timer", self.name, self.current, self.quantity)
     }
 
-    fn increment(&mut self) {
-        self.current += 1;
-        if self.current > self.quantity {
-            self.current = 1;
+    /// Elapsed time with tenths of a second and the total time of the phase.
+    pub fn get_label(&self) -> String {
+        format!("{}/{}", self.time.get_precise_text(), self.time.max_text)
+    }
+}
+
+impl Rest {
+    fn new() -> Rest {
+        Rest {
+            progress: 0,
+            ratio: 0.0,
+            is_finished: false,
+            time: Time::new(),
         }
     }
 
-    fn get_title(&mut self) -> String {
-        format!("Excercise {}/{} Timer", self.current, self.quantity)
+    fn update(&mut self, elapsed: Duration, max: u32) {
+        self.time.update(elapsed, max);
+        self.progress = self.time.get_progress();
+        self.ratio = self.time.get_ratio();
+        self.is_finished = self.time.current >= max;
     }
 
-    fn get_label(&mut self) -> String {
-        format!("{}/{}", self.time.current_text, self.time.max_text)
+    /// Elapsed time with tenths of a second and the total time of the phase.
+    pub fn get_label(&self) -> String {
+        format!("{}/{}", self.time.get_precise_text(), self.time.max_text)
     }
 }
 
-impl Rest {
-    fn new(max_time: u32) -> Rest {
-        let max_time_text: String = get_time_text(max_time);
-        Rest {
-            progress: 0,
-            is_finished: false,
-            time: Time {
-                current: 0,
-                current_text: String::from("00:00:00"),
-                max: max_time,
-                max_text: max_time_text,
-            },
+impl Timer {
+    /// Builds the phases of the workout with [`Plan::new`] and starts at the first one, fails
+    /// when the definition does not pass [`WorkoutDefinition::validate`].
+    pub fn new(definition: WorkoutDefinition) -> Result<Timer, String> {
+        let phases: Vec<Phase> = Plan::new(&definition)?.phases;
+        let mut timer = Timer {
+            set: Set::new(definition.sets.len() as u32),
+            definition,
+            phases,
+            phase_index: 0,
+            phase_elapsed: Duration::ZERO,
+            clock: Duration::ZERO,
+            paused_time: Duration::ZERO,
+            is_paused: false,
+            rounds: 0,
+            work_time: 0,
+            rest_time: 0,
+            workout: Workout::new(),
+            warm_up: Excercise::new(),
+            prepare: Rest::new(),
+            set_rest: Rest::new(),
+            excercise: Excercise::new(),
+            excercise_rest: Rest::new(),
+            cool_down: Excercise::new(),
+            state: TimerState::Set,
+        };
+        timer.update();
+        Ok(timer)
+    }
+
+    // Recalculates all the displayed timers from the current phase and the time spent in it
+    fn update(&mut self) {
+        let phase: &Phase = &self.phases[self.phase_index];
+        self.state = phase.state;
+
+        let workout_max: u32 = self.phases.iter().map(|other| other.time).sum();
+        let workout_current: Duration = get_duration(
+            self.phases[..self.phase_index]
+                .iter()
+                .map(|other| other.time)
+                .sum(),
+        ) + self.phase_elapsed;
+        self.workout.update(workout_current, workout_max);
+
+        let is_set_phase = |other: &Phase| {
+            other.set_index == phase.set_index
+                && (other.state == TimerState::Set || other.state == TimerState::ExcerciseRest)
+        };
+        let set_max: u32 = self
+            .phases
+            .iter()
+            .filter(|other| is_set_phase(other))
+            .map(|other| other.time)
+            .sum();
+        let set_current: Duration = match phase.state {
+            TimerState::WarmUp | TimerState::Prepare => Duration::ZERO,
+            TimerState::SetRest | TimerState::CoolDown => get_duration(set_max),
+            TimerState::Set | TimerState::ExcerciseRest => {
+                get_duration(
+                    self.phases[..self.phase_index]
+                        .iter()
+                        .filter(|other| is_set_phase(other))
+                        .map(|other| other.time)
+                        .sum(),
+                ) + self.phase_elapsed
+            }
+        };
+        self.set.current = phase.set_index as u32 + 1;
+        self.set.update(set_current, set_max);
+
+        match phase.state {
+            TimerState::Set => {
+                let set_definition = &self.definition.sets[phase.set_index];
+                self.excercise.name = set_definition.excercises[phase.excercise_index]
+                    .name
+                    .clone();
+                self.excercise.current = phase.excercise_index as u32 + 1;
+                self.excercise.quantity = set_definition.excercises.len() as u32;
+                self.excercise.update(self.phase_elapsed, phase.time);
+            }
+            TimerState::ExcerciseRest => self.excercise_rest.update(self.phase_elapsed, phase.time),
+            TimerState::SetRest => self.set_rest.update(self.phase_elapsed, phase.time),
+            TimerState::Prepare => self.prepare.update(self.phase_elapsed, phase.time),
+            TimerState::WarmUp => {
+                let items = &self.definition.warm_up;
+                self.warm_up.name = items[phase.excercise_index].name.clone();
+                self.warm_up.current = phase.excercise_index as u32 + 1;
+                self.warm_up.quantity = items.len() as u32;
+                self.warm_up.update(self.phase_elapsed, phase.time);
+            }
+            TimerState::CoolDown => {
+                let items = &self.definition.cool_down;
+                self.cool_down.name = items[phase.excercise_index].name.clone();
+                self.cool_down.current = phase.excercise_index as u32 + 1;
+                self.cool_down.quantity = items.len() as u32;
+                self.cool_down.update(self.phase_elapsed, phase.time);
+            }
         }
     }
 
-    fn on_tick(&mut self, increment: u32) {
-        if self.is_finished == false {
-            self.time.current += increment;
-            self.time.current_text = get_time_text(self.time.current);
-            self.progress = u16::try_from(self.time.current * 100 / self.time.max)
-                .expect("Failed to calculate full time progress");
-            if self.time.current == self.time.max {
-                self.is_finished = true;
+    /// Moves the timer to `clock`, the monotonic time since the workout started, and returns the
+    /// cues to announce.
+    ///
+    /// Phase times are the clock minus the intervals spent paused, so they never drift from the
+    /// clock no matter how often it is called. A late call catches up on every phase that ended
+    /// in the meantime and only announces the cues from the last phase change on.
+    pub fn advance_to(&mut self, clock: Duration) -> Vec<Cue> {
+        let elapsed = clock.saturating_sub(self.clock);
+        self.clock = self.clock.max(clock);
+        if self.is_paused {
+            self.paused_time += elapsed;
+            return Vec::new();
+        }
+        self.catch_up(elapsed)
+    }
+
+    /// Moves the timer forward by `elapsed`, see [`Timer::advance_to`].
+    pub fn advance(&mut self, elapsed: Duration) -> Vec<Cue> {
+        self.advance_to(self.clock + elapsed)
+    }
+
+    fn catch_up(&mut self, mut elapsed: Duration) -> Vec<Cue> {
+        let mut cues: Vec<Cue> = Vec::new();
+        while !self.workout.is_finished && !elapsed.is_zero() {
+            let phase_length = get_duration(self.phases[self.phase_index].time);
+            let step = elapsed.min(phase_length.saturating_sub(self.phase_elapsed));
+            elapsed -= step;
+            let seconds = (self.phase_elapsed + step).as_secs() - self.phase_elapsed.as_secs();
+            if self.state.is_work() {
+                self.work_time += seconds as u32;
+            } else {
+                self.rest_time += seconds as u32;
+            }
+            self.phase_elapsed += step;
+            if self.phase_elapsed >= phase_length && self.phase_index + 1 < self.phases.len() {
+                self.phase_index += 1;
+                self.phase_elapsed = Duration::ZERO;
+                self.update();
+                // Cues of the phases already left behind are not worth announcing
+                cues.clear();
+                cues.extend(self.get_phase_cue());
+                continue;
+            }
+            self.update();
+            if self.workout.is_finished {
+                cues.push(Cue::WorkoutDone);
+            } else if seconds > 0 && self.get_remaining_time() <= COUNTDOWN_TIME {
+                cues.push(Cue::Countdown);
             }
         }
+        cues
     }
 
-    fn clear(&mut self) {
-        self.is_finished = false;
-        self.progress = 0;
-        self.time.current = 0;
-        self.time.current_text = get_time_text(0);
+    /// Cue announcing the start of the current phase.
+    pub fn get_phase_cue(&self) -> Option<Cue> {
+        match self.state {
+            TimerState::Set | TimerState::WarmUp => Some(Cue::WorkStart),
+            TimerState::ExcerciseRest => Some(Cue::RestStart),
+            TimerState::SetRest | TimerState::CoolDown => Some(Cue::SetDone),
+            TimerState::Prepare => None,
+        }
     }
 
-    fn get_label(&mut self) -> String {
-        format!("{}/{}", self.time.current_text, self.time.max_text)
+    /// Definition the timer was built from.
+    pub fn get_definition(&self) -> &WorkoutDefinition {
+        &self.definition
     }
-}
 
-impl Timer {
-    pub fn new(
-        excercise_time: u32,
-        excercise_quantity: u32,
-        excercise_rest_time: u32,
-        set_quantity: u32,
-        set_rest_time: u32,
-    ) -> Timer {
-        let mut transitions = 2 * excercise_quantity - 2;
-        let set_time = excercise_quantity * excercise_time
-            + (excercise_quantity - 1) * excercise_rest_time
-            + transitions;
-        transitions = 2 * set_quantity - 2;
-        let max_time = set_quantity * set_time + (set_quantity - 1) * set_rest_time + transitions;
-        Timer {
-            workout: Workout::new(max_time),
-            set: Set::new(set_time, set_quantity),
-            set_rest: Rest::new(set_rest_time),
-            excercise: Excercise::new(excercise_time, excercise_quantity),
-            excercise_rest: Rest::new(excercise_rest_time),
-            state: TimerState::Set,
+    /// Kind of the current phase.
+    pub fn get_state(&self) -> TimerState {
+        self.state
+    }
+
+    /// Every phase of the workout, changed by [`Timer::complete`] in EMOM workouts.
+    pub fn get_phases(&self) -> &[Phase] {
+        &self.phases
+    }
+
+    /// Current phase.
+    pub fn get_phase(&self) -> &Phase {
+        &self.phases[self.phase_index]
+    }
+
+    /// Phase that starts after the current one, `None` during the last phase.
+    pub fn get_upcoming_phase(&self) -> Option<&Phase> {
+        self.phases.get(self.phase_index + 1)
+    }
+
+    /// Up to `count` phases that follow the current one, in order.
+    pub fn get_upcoming_phases(&self, count: usize) -> &[Phase] {
+        let start = self.phase_index + 1;
+        &self.phases[start..(start + count).min(self.phases.len())]
+    }
+
+    /// Whole seconds spent in the current phase.
+    pub fn get_phase_time(&self) -> u32 {
+        self.phase_elapsed.as_secs() as u32
+    }
+
+    /// Precise time spent in the current phase.
+    pub fn get_phase_elapsed(&self) -> Duration {
+        self.phase_elapsed
+    }
+
+    /// Time spent in the workout without the paused intervals.
+    pub fn get_active_time(&self) -> Duration {
+        self.clock - self.paused_time
+    }
+
+    /// Seconds left in the current phase.
+    pub fn get_remaining_time(&self) -> u32 {
+        self.phases[self.phase_index]
+            .time
+            .saturating_sub(self.get_phase_time())
+    }
+
+    /// Seconds left until the end of the workout.
+    pub fn get_workout_remaining_time(&self) -> u32 {
+        self.workout.time.max - self.workout.time.current
+    }
+
+    /// Progress of the whole workout in percents.
+    pub fn get_progress(&self) -> u16 {
+        self.workout.progress
+    }
+
+    /// Progress of the whole workout.
+    pub fn get_workout(&self) -> &Workout {
+        &self.workout
+    }
+
+    /// Progress of the current set.
+    pub fn get_set(&self) -> &Set {
+        &self.set
+    }
+
+    /// Current excercise, kept after the excercise ends so rests can refer to it.
+    pub fn get_excercise(&self) -> &Excercise {
+        &self.excercise
+    }
+
+    /// Current or last rest between excercises.
+    pub fn get_excercise_rest(&self) -> &Rest {
+        &self.excercise_rest
+    }
+
+    /// Current or last rest between sets.
+    pub fn get_set_rest(&self) -> &Rest {
+        &self.set_rest
+    }
+
+    /// Current or last get ready countdown.
+    pub fn get_prepare(&self) -> &Rest {
+        &self.prepare
+    }
+
+    /// Current or last warm up item.
+    pub fn get_warm_up(&self) -> &Excercise {
+        &self.warm_up
+    }
+
+    /// Current or last cool down item.
+    pub fn get_cool_down(&self) -> &Excercise {
+        &self.cool_down
+    }
+
+    /// Rounds counted with [`Timer::add_round`] in AMRAP and for time workouts.
+    pub fn get_rounds(&self) -> u32 {
+        self.rounds
+    }
+
+    /// Whether the time stands still, see [`Timer::toggle_pause`].
+    pub fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    /// Whole seconds spent working, skipped time not included.
+    pub fn get_work_time(&self) -> u32 {
+        self.work_time
+    }
+
+    /// Whole seconds spent resting and getting ready, skipped time not included.
+    pub fn get_rest_time(&self) -> u32 {
+        self.rest_time
+    }
+
+    /// Whether the last phase has ended, a finished timer does not move anymore.
+    pub fn is_finished(&self) -> bool {
+        self.workout.is_finished
+    }
+
+    /// Excercises and sets are completed once the timer moves past them, skipping included.
+    pub fn get_completed_excercises(&self) -> u32 {
+        self.phases
+            .iter()
+            .enumerate()
+            .filter(|(index, phase)| phase.state == TimerState::Set && self.is_completed(*index))
+            .count() as u32
+    }
+
+    /// Sets whose every excercise is completed.
+    pub fn get_completed_sets(&self) -> u32 {
+        (0..self.definition.sets.len())
+            .filter(|set_index| {
+                self.phases.iter().enumerate().all(|(index, phase)| {
+                    phase.set_index != *set_index
+                        || phase.state != TimerState::Set
+                        || self.is_completed(index)
+                })
+            })
+            .count() as u32
+    }
+
+    fn is_completed(&self, phase_index: usize) -> bool {
+        phase_index < self.phase_index || (phase_index == self.phase_index && self.is_finished())
+    }
+
+    /// Human readable name of the current phase, like `Set 1/3 Excercise 2/10`, other modes
+    /// than intervals use [`Phase::get_name`].
+    pub fn get_phase_name(&self) -> String {
+        if self.definition.mode != Mode::Intervals {
+            return self.phases[self.phase_index].get_name(&self.definition);
+        }
+        let set = format!("Set {}/{}", self.set.current, self.set.quantity);
+        match self.state {
+            TimerState::WarmUp => format!(
+                "Warm up {} {}/{}",
+                self.warm_up.name, self.warm_up.current, self.warm_up.quantity
+            ),
+            TimerState::Prepare => format!("Get ready for {}", set),
+            TimerState::Set => format!(
+                "{} {} {}/{}",
+                set, self.excercise.name, self.excercise.current, self.excercise.quantity
+            ),
+            TimerState::ExcerciseRest => format!("{} rest between excercises", set),
+            TimerState::SetRest => format!("{} rest", set),
+            TimerState::CoolDown => format!(
+                "Cool down {} {}/{}",
+                self.cool_down.name, self.cool_down.current, self.cool_down.quantity
+            ),
         }
     }
 
-    fn on_tick(&mut self) {
-        let mut set_increment: u32 = 1;
-        let mut set_rest_increment: u32 = 1;
-        let mut excercise_increment: u32 = 1;
-        let mut excercise_rest_increment: u32 = 1;
-        if self.state == TimerState::Set && self.set.is_finished {
-            self.state = TimerState::SetRest;
-            self.set.clear();
-            self.set.increment();
-            self.excercise.clear();
-            self.excercise.increment();
-            set_rest_increment = 0;
-        } else if self.state == TimerState::Set && self.excercise.is_finished {
-            self.state = TimerState::ExcerciseRest;
-            self.excercise.clear();
-            self.excercise.increment();
-            excercise_rest_increment = 0;
-        } else if self.state == TimerState::SetRest && self.set_rest.is_finished {
-            self.state = TimerState::Set;
-            self.set_rest.clear();
-            set_increment = 0;
-            excercise_increment = 0;
-        } else if self.state == TimerState::ExcerciseRest && self.excercise_rest.is_finished {
-            self.state = TimerState::Set;
-            self.excercise_rest.clear();
-            excercise_increment = 0;
+    /// Stops or resumes the time, paused intervals are not counted by [`Timer::advance_to`].
+    pub fn toggle_pause(&mut self) {
+        self.is_paused = !self.is_paused;
+    }
+
+    /// Jumps to the start of the next phase, or to the end of the last one.
+    pub fn skip(&mut self) {
+        if self.phase_index + 1 < self.phases.len() {
+            self.phase_index += 1;
+            self.phase_elapsed = Duration::ZERO;
+        } else {
+            self.phase_elapsed = get_duration(self.phases[self.phase_index].time);
         }
+        self.update();
+    }
+
+    /// Jumps to the start of the previous phase.
+    pub fn rewind(&mut self) {
+        self.phase_index = self.phase_index.saturating_sub(1);
+        self.phase_elapsed = Duration::ZERO;
+        self.update();
+    }
+
+    /// Makes the current phase 10 seconds longer, a finished workout stays finished.
+    pub fn add_time(&mut self) {
+        if self.is_finished() {
+            return;
+        }
+        self.phases[self.phase_index].time += TIME_STEP;
+        self.update();
+    }
+
+    /// Makes the current phase 10 seconds shorter, keeping at least one second left.
+    pub fn subtract_time(&mut self) {
+        if self.is_finished() {
+            return;
+        }
+        let phase: &mut Phase = &mut self.phases[self.phase_index];
+        phase.time = phase
+            .time
+            .saturating_sub(TIME_STEP)
+            .max(self.phase_elapsed.as_secs() as u32 + 1);
+        self.update();
+    }
 
-        self.workout.on_tick();
-        if self.state == TimerState::Set {
-            self.set.on_tick(set_increment);
-            self.excercise.on_tick(excercise_increment);
+    /// Counts a round in AMRAP and for time workouts, other modes ignore it.
+    pub fn add_round(&mut self) {
+        if self.definition.mode == Mode::Amrap || self.definition.mode == Mode::ForTime {
+            self.rounds += 1;
         }
-        if self.state == TimerState::SetRest {
-            self.set_rest.on_tick(set_rest_increment);
+    }
+
+    /// Marks the work as done, for EMOM the rest of the interval becomes a rest and for time
+    /// finishes the whole workout. Work that has already ended can not be completed again.
+    pub fn complete(&mut self) -> Option<Cue> {
+        let phase_time = self.get_phase_time();
+        if self.state != TimerState::Set
+            || phase_time == 0
+            || self.get_remaining_time() == 0
+            || self.is_finished()
+        {
+            return None;
         }
-        if self.state == TimerState::ExcerciseRest {
-            self.set.on_tick(set_increment);
-            self.excercise_rest.on_tick(excercise_rest_increment);
+        let phase: &mut Phase = &mut self.phases[self.phase_index];
+        match self.definition.mode {
+            Mode::Emom => {
+                let rest_phase = Phase {
+                    state: TimerState::ExcerciseRest,
+                    set_index: phase.set_index,
+                    excercise_index: phase.excercise_index,
+                    time: phase.time - phase_time,
+                };
+                phase.time = phase_time;
+                self.phases.insert(self.phase_index + 1, rest_phase);
+                self.phase_index += 1;
+                self.phase_elapsed = Duration::ZERO;
+                self.update();
+                Some(Cue::RestStart)
+            }
+            Mode::ForTime => {
+                phase.time = phase_time;
+                self.phase_elapsed = get_duration(phase_time);
+                if self.phase_index + 1 < self.phases.len() {
+                    self.phase_index += 1;
+                    self.phase_elapsed = Duration::ZERO;
+                    self.update();
+                    return self.get_phase_cue();
+                }
+                self.update();
+                Some(Cue::WorkoutDone)
+            }
+            Mode::Intervals | Mode::Tabata | Mode::Amrap => None,
         }
     }
 }
 
-fn get_time_text(mut time: u32) -> String {
+fn get_duration(time: u32) -> Duration {
+    Duration::from_secs(u64::from(time))
+}
+
+/// Formats seconds as `hh:mm:ss`.
+pub fn get_time_text(mut time: u32) -> String {
     let hours: u32 = time / 60 / 60;
     time -= hours * 60 * 60;
     let minutes: u32 = time / 60;
@@ -312,128 +832,210 @@ fn get_time_text(mut time: u32) -> String {
     format!("{hours:02}:{minutes:02}:{time:02}")
 }
 
-pub fn run<B: Backend>(terminal: &mut Terminal<B>, mut timer: Timer) -> io::Result<()> {
-    // let mut timer: Timer = Timer::new(10, 2, 5, 5, 10);
-    let mut last_tick = Instant::now();
-    loop {
-        terminal.draw(|frame: &mut Frame<'_, B>| tui(frame, &mut timer))?;
-
-        let timeout = TICK_RATE
-            .checked_sub(last_tick.elapsed())
-            .unwrap_or_else(|| Duration::from_secs(0));
-        if crossterm::event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                if let KeyCode::Char('q') = key.code {
-                    return Ok(());
-                }
-            }
+/// Formats seconds compactly, like `45s`, `2m` or `1m 30s`.
+pub fn get_short_time_text(time: u32) -> String {
+    match (time / 60, time % 60) {
+        (0, seconds) => format!("{}s", seconds),
+        (minutes, 0) => format!("{}m", minutes),
+        (minutes, seconds) => format!("{}m {}s", minutes, seconds),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Cue, Timer, TimerState};
+    use crate::workout::WorkoutDefinition;
+
+    #[test]
+    fn ticks_produce_transition_and_countdown_cues() {
+        let mut timer = Timer::new(WorkoutDefinition::from_options(4, 2, 1, 2, 2, 0, 0)).unwrap();
+        let mut cues: Vec<Cue> = Vec::new();
+        while !timer.is_finished() {
+            cues.extend(timer.advance(Duration::from_secs(1)));
         }
-        if last_tick.elapsed() >= TICK_RATE && timer.workout.is_finished == false {
-            timer.on_tick();
-            last_tick = Instant::now();
+        let set = [
+            Cue::Countdown,
+            Cue::Countdown,
+            Cue::Countdown,
+            Cue::RestStart,
+            Cue::WorkStart,
+            Cue::Countdown,
+            Cue::Countdown,
+            Cue::Countdown,
+        ];
+        let mut expected: Vec<Cue> = set.to_vec();
+        expected.extend([Cue::SetDone, Cue::Countdown, Cue::WorkStart]);
+        expected.extend(set);
+        expected.push(Cue::WorkoutDone);
+        assert_eq!(cues, expected);
+    }
+
+    #[test]
+    fn invalid_definitions_are_rejected() {
+        let mut definition = WorkoutDefinition::from_options(30, 0, 10, 2, 60, 0, 0);
+        assert_eq!(
+            Timer::new(definition.clone()).err(),
+            Some(String::from("Set 1 needs at least 1 excercise"))
+        );
+        definition.sets.clear();
+        assert_eq!(
+            Timer::new(definition).err(),
+            Some(String::from("Workout needs at least 1 set"))
+        );
+        let definition = WorkoutDefinition::from_options(0, 1, 0, 1, 0, 0, 0);
+        assert_eq!(
+            Timer::new(definition).err(),
+            Some(String::from("Excercise in set 1 needs at least 1 second"))
+        );
+        let definition = WorkoutDefinition::amrap(7200, 0);
+        let mut long_definition = definition.clone();
+        long_definition.sets = vec![definition.sets[0].clone(); 13];
+        assert_eq!(
+            Timer::new(long_definition).err(),
+            Some(String::from("Workout can not be longer than 24 hours"))
+        );
+        assert!(Timer::new(WorkoutDefinition::from_options(1, 1, 0, 1, 0, 0, 0)).is_ok());
+        // Rests after the last excercise and set are never done, so they do not count
+        let mut definition = WorkoutDefinition::from_options(60, 1, 0, 1, 0, 0, 0);
+        definition.sets[0].rest_time = 86_400;
+        definition.sets[0].excercises[0].rest_time = 86_400;
+        definition.set_prepare_time = 86_400;
+        assert!(Timer::new(definition).is_ok());
+    }
+
+    #[test]
+    fn phase_names_follow_the_mode() {
+        let names = |definition: WorkoutDefinition| -> Vec<String> {
+            let timer = Timer::new(definition.clone()).unwrap();
+            let mut names = vec![timer.get_phase_name()];
+            names.extend(
+                timer
+                    .get_upcoming_phases(2)
+                    .iter()
+                    .map(|phase| phase.get_name(&definition)),
+            );
+            names
+        };
+        assert_eq!(
+            names(WorkoutDefinition::from_options(20, 2, 10, 2, 0, 0, 0)),
+            ["Set 1/2 Excercise 1/2", "Rest", "Set 1 · Excercise 2"]
+        );
+        assert_eq!(
+            names(WorkoutDefinition::tabata(20, 10, 8, 0)),
+            ["Tabata Round 1/8", "Tabata Rest 1/8", "Tabata Round 2/8"]
+        );
+        assert_eq!(
+            names(WorkoutDefinition::emom(60, 10, 5)),
+            ["Get ready", "EMOM Interval 1/10", "EMOM Interval 2/10"]
+        );
+    }
+
+    #[test]
+    fn excercise_rest_leads_to_next_excercise() {
+        let mut timer = Timer::new(WorkoutDefinition::from_options(4, 2, 1, 1, 0, 0, 0)).unwrap();
+        assert_eq!(timer.advance(Duration::from_secs(4)), vec![Cue::RestStart]);
+        assert_eq!(timer.get_state(), TimerState::ExcerciseRest);
+        assert_eq!(timer.get_excercise().current, 1);
+        assert_eq!(timer.advance(Duration::from_secs(1)), vec![Cue::WorkStart]);
+        assert_eq!(timer.get_state(), TimerState::Set);
+        assert_eq!(timer.get_excercise().current, 2);
+    }
+
+    #[test]
+    fn last_excercise_leads_to_set_rest() {
+        let mut timer = Timer::new(WorkoutDefinition::from_options(2, 2, 1, 2, 30, 0, 0)).unwrap();
+        timer.advance(Duration::from_secs(3));
+        assert_eq!(timer.advance(Duration::from_secs(2)), vec![Cue::SetDone]);
+        assert_eq!(timer.get_state(), TimerState::SetRest);
+        assert_eq!(timer.get_set().current, 1);
+        assert!(timer.get_set().is_finished);
+        assert_eq!(timer.get_set_rest().time.max, 30);
+        assert_eq!(timer.advance(Duration::from_secs(30)), vec![Cue::WorkStart]);
+        assert_eq!(timer.get_set().current, 2);
+        assert_eq!(timer.get_excercise().current, 1);
+    }
+
+    #[test]
+    fn last_set_finishes_workout() {
+        let mut timer = Timer::new(WorkoutDefinition::from_options(2, 1, 0, 2, 1, 0, 0)).unwrap();
+        timer.advance(Duration::from_secs(3));
+        assert_eq!(timer.get_set().current, 2);
+        assert_eq!(timer.advance(Duration::from_secs(1)), vec![Cue::Countdown]);
+        assert!(!timer.is_finished());
+        assert_eq!(
+            timer.advance(Duration::from_secs(1)),
+            vec![Cue::WorkoutDone]
+        );
+        assert!(timer.is_finished());
+        assert_eq!(timer.get_completed_sets(), 2);
+        assert_eq!(timer.get_progress(), 100);
+        assert_eq!(timer.advance(Duration::from_secs(1)), vec![]);
+        // Changing the time of a finished workout would start it again
+        timer.add_time();
+        timer.subtract_time();
+        assert!(timer.is_finished());
+        assert_eq!(timer.advance(Duration::from_secs(1)), vec![]);
+    }
+
+    #[test]
+    fn phase_times_follow_the_clock_without_paused_intervals() {
+        let mut timer = Timer::new(WorkoutDefinition::from_options(10, 2, 5, 1, 0, 0, 0)).unwrap();
+        for step in 1..=5 {
+            timer.advance_to(Duration::from_millis(700 * step));
         }
+        assert_eq!(timer.get_phase_elapsed(), Duration::from_millis(3500));
+        assert_eq!(timer.get_excercise().get_label(), "00:00:03.5/00:00:10");
+        timer.toggle_pause();
+        assert_eq!(timer.advance_to(Duration::from_secs(60)), vec![]);
+        timer.toggle_pause();
+        // A late call catches up on the ended excercise and only announces the rest
+        assert_eq!(
+            timer.advance_to(Duration::from_secs(68)),
+            vec![Cue::RestStart]
+        );
+        assert_eq!(timer.get_active_time(), Duration::from_millis(11500));
+        assert_eq!(timer.get_phase_elapsed(), Duration::from_millis(1500));
+        assert_eq!(timer.get_work_time(), 10);
+        assert_eq!(timer.get_rest_time(), 1);
+        assert_eq!(
+            timer.advance_to(Duration::from_secs(75)),
+            vec![Cue::WorkStart]
+        );
+        assert!(timer.get_upcoming_phase().is_none());
     }
-}
 
-fn tui<B: Backend>(frame: &mut Frame<B>, timer: &mut Timer) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints(
-            [
-                Constraint::Max(4),
-                Constraint::Max(4),
-                Constraint::Max(4),
-                Constraint::Max(4),
-                Constraint::Max(4),
-            ]
-            .as_ref(),
-        )
-        .split(frame.size());
-    // Header info
-    let mut text = Text::styled(
-        COMMON_HEADER,
-        Style::default().add_modifier(Modifier::ITALIC),
-    );
-    text.extend(Text::raw(TIMER_HEADER));
-    frame.render_widget(Paragraph::new(text).block(Block::new()), chunks[0]);
-    // Full Workout Timer
-    let gauge = Gauge::default()
-        .block(
-            Block::default()
-                .title("Full Workout Timer")
-                .borders(Borders::ALL),
-        )
-        .gauge_style(
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
-        )
-        .percent(timer.workout.progress)
-        .label(timer.workout.get_label());
-    frame.render_widget(gauge, chunks[1]);
-
-    if timer.state == TimerState::Set || timer.state == TimerState::ExcerciseRest {
-        // Set Timer
-        let gauge = Gauge::default()
-            .block(
-                Block::default()
-                    .title(timer.set.get_title())
-                    .borders(Borders::ALL),
-            )
-            .gauge_style(
-                Style::default()
-                    .fg(Color::Rgb(255, 140, 0))
-                    .add_modifier(Modifier::BOLD),
-            )
-            .percent(timer.set.progress)
-            .label(timer.set.get_label());
-        frame.render_widget(gauge, chunks[2]);
-    } else if timer.state == TimerState::SetRest {
-        // Set Rest Timer
-        let gauge = Gauge::default()
-            .block(
-                Block::default()
-                    .title("Set Rest Timer")
-                    .borders(Borders::ALL),
-            )
-            .gauge_style(
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .percent(timer.set_rest.progress)
-            .label(timer.set_rest.get_label());
-        frame.render_widget(gauge, chunks[2]);
-    }
-
-    if timer.state == TimerState::SetRest {
-    } else if timer.state == TimerState::Set {
-        // Excercise Timer
-        let gauge = Gauge::default()
-            .block(
-                Block::default()
-                    .title(timer.excercise.get_title())
-                    .borders(Borders::ALL),
-            )
-            .gauge_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
-            .percent(timer.excercise.progress)
-            .label(timer.excercise.get_label());
-        frame.render_widget(gauge, chunks[3]);
-    } else if timer.state == TimerState::ExcerciseRest {
-        // Excercise Rest Timer
-        let gauge = Gauge::default()
-            .block(
-                Block::default()
-                    .title("Excercise Rest Timer")
-                    .borders(Borders::ALL),
-            )
-            .gauge_style(
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .percent(timer.excercise_rest.progress)
-            .label(timer.excercise_rest.get_label());
-        frame.render_widget(gauge, chunks[3]);
+    #[test]
+    fn completing_emom_work_rests_until_next_interval() {
+        let mut timer = Timer::new(WorkoutDefinition::emom(10, 2, 0)).unwrap();
+        timer.advance(Duration::from_secs(4));
+        assert_eq!(timer.complete(), Some(Cue::RestStart));
+        assert_eq!(timer.get_state(), TimerState::ExcerciseRest);
+        assert_eq!(timer.get_excercise_rest().time.max, 6);
+        assert_eq!(
+            timer.get_upcoming_phase().map(|phase| phase.state),
+            Some(TimerState::Set)
+        );
+        assert_eq!(timer.get_upcoming_phases(3).len(), 1);
+        timer.advance(Duration::from_secs(6));
+        assert_eq!(timer.get_state(), TimerState::Set);
+        assert_eq!(timer.get_set().current, 2);
+        assert_eq!(timer.get_workout().time.current, 10);
+        assert_eq!(timer.get_workout().time.max, 20);
+    }
+
+    #[test]
+    fn completing_ended_work_does_nothing() {
+        let mut timer = Timer::new(WorkoutDefinition::emom(10, 2, 0)).unwrap();
+        timer.advance(Duration::from_secs(25));
+        assert!(timer.is_finished());
+        assert_eq!(timer.complete(), None);
+        assert_eq!(timer.get_progress(), 100);
+        let mut timer = Timer::new(WorkoutDefinition::for_time(60, 0)).unwrap();
+        timer.advance(Duration::from_secs(5));
+        assert_eq!(timer.complete(), Some(Cue::WorkoutDone));
+        assert_eq!(timer.complete(), None);
+        assert!(timer.is_finished());
     }
 }
@@ -0,0 +1,321 @@
+//! Workout definitions, built from options or loaded from TOML and JSON files.
+
+use std::{fs, path::Path};
+
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+const DEFAULT_EXCERCISE_NAME: &str = "Excercise";
+const TABATA_EXCERCISE_NAME: &str = "Tabata Round";
+const EMOM_EXCERCISE_NAME: &str = "Interval";
+const AMRAP_EXCERCISE_NAME: &str = "AMRAP";
+const FOR_TIME_EXCERCISE_NAME: &str = "For Time";
+// Keeps the sums of phase times far from overflowing
+const MAX_WORKOUT_TIME: u32 = 24 * 60 * 60;
+
+/// Every mode in the order the options screen steps through them.
+pub const MODES: [Mode; 5] = [
+    Mode::Intervals,
+    Mode::Tabata,
+    Mode::Emom,
+    Mode::Amrap,
+    Mode::ForTime,
+];
+
+/// Kind of workout, deciding how the sets and excercises are named and shown.
+#[derive(Serialize, Deserialize, Default, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Mode {
+    /// Sets of excercises with rests in between.
+    #[default]
+    Intervals,
+    /// Short work and rest intervals repeated for a number of rounds.
+    Tabata,
+    /// Every set is one interval, the time left after the work is done is a rest.
+    Emom,
+    /// Single countdown with a round counter.
+    Amrap,
+    /// Single count up ended when the work is done or the time cap is reached.
+    ForTime,
+}
+
+/// Named warm up or cool down item.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ItemDefinition {
+    /// Name shown while the item is done.
+    pub name: String,
+    /// Length of the item in seconds.
+    #[serde(deserialize_with = "deserialize_time")]
+    pub time: u32,
+}
+
+/// Excercise of a set with the rest that follows it.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ExcerciseDefinition {
+    /// Name shown while the excercise is done, `Excercise` when left out.
+    #[serde(default = "get_default_excercise_name")]
+    pub name: String,
+    /// Work time in seconds.
+    #[serde(deserialize_with = "deserialize_time")]
+    pub time: u32,
+    /// Rest after the excercise in seconds, the last excercise in a set is followed by the set
+    /// rest instead.
+    #[serde(default)]
+    pub rest_time: u32,
+}
+
+/// Excercises done one after another, with the rest after them.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SetDefinition {
+    /// Excercises in the order they are done, at least one.
+    #[serde(alias = "exercises", deserialize_with = "deserialize_non_empty")]
+    pub excercises: Vec<ExcerciseDefinition>,
+    /// Rest after the set in seconds, ignored for the last set.
+    #[serde(default)]
+    pub rest_time: u32,
+}
+
+/// Whole workout, everything a [`Timer`](crate::timer::Timer) needs to run it.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct WorkoutDefinition {
+    /// Kind of the workout, intervals when left out.
+    #[serde(default)]
+    pub mode: Mode,
+    /// Items done one after another without rests before the first set.
+    #[serde(default)]
+    pub warm_up: Vec<ItemDefinition>,
+    /// Sets in the order they are done, at least one.
+    #[serde(deserialize_with = "deserialize_non_empty")]
+    pub sets: Vec<SetDefinition>,
+    /// Items done one after another without rests after the last set.
+    #[serde(default)]
+    pub cool_down: Vec<ItemDefinition>,
+    /// Get ready countdown before the first set in seconds, zero disables it.
+    #[serde(default)]
+    pub prepare_time: u32,
+    /// Get ready countdown after every rest between sets in seconds, zero disables it.
+    #[serde(default)]
+    pub set_prepare_time: u32,
+}
+
+fn get_default_excercise_name() -> String {
+    DEFAULT_EXCERCISE_NAME.to_string()
+}
+
+// Validation happens while deserializing so that the parsers can point at the offending line
+fn deserialize_time<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    let time = u32::deserialize(deserializer)?;
+    if time == 0 {
+        return Err(de::Error::custom("time must be at least 1 second"));
+    }
+    Ok(time)
+}
+
+fn deserialize_non_empty<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    let items = Vec::<T>::deserialize(deserializer)?;
+    if items.is_empty() {
+        return Err(de::Error::custom("list must contain at least one element"));
+    }
+    Ok(items)
+}
+
+impl Mode {
+    /// Name of the mode as the options screen shows it.
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Mode::Intervals => "Intervals",
+            Mode::Tabata => "Tabata",
+            Mode::Emom => "EMOM",
+            Mode::Amrap => "AMRAP",
+            Mode::ForTime => "For Time",
+        }
+    }
+}
+
+impl ExcerciseDefinition {
+    fn new(name: &str, time: u32, rest_time: u32) -> ExcerciseDefinition {
+        ExcerciseDefinition {
+            name: name.to_string(),
+            time,
+            rest_time,
+        }
+    }
+}
+
+fn validate_items(items: &[ItemDefinition], kind: &str) -> Result<(), String> {
+    for item in items {
+        if item.time == 0 {
+            return Err(format!("{} {} needs at least 1 second", kind, item.name));
+        }
+    }
+    Ok(())
+}
+
+impl WorkoutDefinition {
+    fn new(mode: Mode, sets: Vec<SetDefinition>, prepare_time: u32) -> WorkoutDefinition {
+        WorkoutDefinition {
+            mode,
+            warm_up: Vec::new(),
+            sets,
+            cool_down: Vec::new(),
+            prepare_time,
+            set_prepare_time: 0,
+        }
+    }
+
+    /// Intervals workout of equal sets with equal excercises.
+    pub fn from_options(
+        excercise_time: u32,
+        excercise_quantity: u32,
+        excercise_rest_time: u32,
+        set_quantity: u32,
+        set_rest_time: u32,
+        prepare_time: u32,
+        set_prepare_time: u32,
+    ) -> WorkoutDefinition {
+        let sets = (0..set_quantity)
+            .map(|_| SetDefinition {
+                excercises: (0..excercise_quantity)
+                    .map(|_| {
+                        ExcerciseDefinition::new(
+                            DEFAULT_EXCERCISE_NAME,
+                            excercise_time,
+                            excercise_rest_time,
+                        )
+                    })
+                    .collect(),
+                rest_time: set_rest_time,
+            })
+            .collect();
+        WorkoutDefinition {
+            set_prepare_time,
+            ..WorkoutDefinition::new(Mode::Intervals, sets, prepare_time)
+        }
+    }
+
+    /// Single set of work and rest rounds.
+    pub fn tabata(
+        work_time: u32,
+        rest_time: u32,
+        rounds: u32,
+        prepare_time: u32,
+    ) -> WorkoutDefinition {
+        let set = SetDefinition {
+            excercises: (0..rounds)
+                .map(|_| ExcerciseDefinition::new(TABATA_EXCERCISE_NAME, work_time, rest_time))
+                .collect(),
+            rest_time: 0,
+        };
+        WorkoutDefinition::new(Mode::Tabata, vec![set], prepare_time)
+    }
+
+    /// Work starting every interval, each interval is its own set.
+    pub fn emom(interval_time: u32, intervals: u32, prepare_time: u32) -> WorkoutDefinition {
+        let sets = (0..intervals)
+            .map(|_| SetDefinition {
+                excercises: vec![ExcerciseDefinition::new(
+                    EMOM_EXCERCISE_NAME,
+                    interval_time,
+                    0,
+                )],
+                rest_time: 0,
+            })
+            .collect();
+        WorkoutDefinition::new(Mode::Emom, sets, prepare_time)
+    }
+
+    /// As many rounds as possible in the given time.
+    pub fn amrap(time: u32, prepare_time: u32) -> WorkoutDefinition {
+        let set = SetDefinition {
+            excercises: vec![ExcerciseDefinition::new(AMRAP_EXCERCISE_NAME, time, 0)],
+            rest_time: 0,
+        };
+        WorkoutDefinition::new(Mode::Amrap, vec![set], prepare_time)
+    }
+
+    /// Work finished as fast as possible, limited by the time cap.
+    pub fn for_time(time_cap: u32, prepare_time: u32) -> WorkoutDefinition {
+        let set = SetDefinition {
+            excercises: vec![ExcerciseDefinition::new(
+                FOR_TIME_EXCERCISE_NAME,
+                time_cap,
+                0,
+            )],
+            rest_time: 0,
+        };
+        WorkoutDefinition::new(Mode::ForTime, vec![set], prepare_time)
+    }
+
+    /// Checks that the workout can be timed, definitions built in code skip the checks done
+    /// while loading files.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.sets.is_empty() {
+            return Err(String::from("Workout needs at least 1 set"));
+        }
+        validate_items(&self.warm_up, "Warm up")?;
+        validate_items(&self.cool_down, "Cool down")?;
+        let mut total_time: u64 = self
+            .warm_up
+            .iter()
+            .chain(self.cool_down.iter())
+            .map(|item| u64::from(item.time))
+            .sum();
+        total_time += u64::from(self.prepare_time);
+        for (set_index, set) in self.sets.iter().enumerate() {
+            if set.excercises.is_empty() {
+                return Err(format!("Set {} needs at least 1 excercise", set_index + 1));
+            }
+            for (excercise_index, excercise) in set.excercises.iter().enumerate() {
+                if excercise.time == 0 {
+                    return Err(format!(
+                        "{} in set {} needs at least 1 second",
+                        excercise.name,
+                        set_index + 1
+                    ));
+                }
+                total_time += u64::from(excercise.time);
+                // Rests only come between excercises and between sets, like in the plan
+                if excercise_index + 1 < set.excercises.len() {
+                    total_time += u64::from(excercise.rest_time);
+                }
+            }
+            if set_index + 1 < self.sets.len() {
+                total_time += u64::from(set.rest_time) + u64::from(self.set_prepare_time);
+            }
+        }
+        if total_time > u64::from(MAX_WORKOUT_TIME) {
+            return Err(String::from("Workout can not be longer than 24 hours"));
+        }
+        Ok(())
+    }
+}
+
+/// Reads a workout from a `.toml` or `.json` file.
+pub fn load(path: &Path) -> Result<WorkoutDefinition, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|error| format!("Failed to read {}: {}", path.display(), error))?;
+    let extension = path.extension().and_then(|extension| extension.to_str());
+    let definition: WorkoutDefinition = match extension {
+        Some("toml") => toml::from_str(&content)
+            .map_err(|error| format!("Invalid workout {}: {}", path.display(), error))?,
+        Some("json") => serde_json::from_str(&content)
+            .map_err(|error| format!("Invalid workout {}: {}", path.display(), error))?,
+        _ => {
+            return Err(format!(
+                "Unsupported workout file {}, expected .toml or .json extension",
+                path.display()
+            ))
+        }
+    };
+    definition
+        .validate()
+        .map_err(|error| format!("Invalid workout {}: {}", path.display(), error))?;
+    Ok(definition)
+}